@@ -0,0 +1,40 @@
+//! Compares the generated `from_name` with a linear scan over `names()`
+//! on a 60-flag type. Run with `cargo bench --bench from_name`.
+
+use std::hint::black_box;
+use std::time::Instant;
+use tlbf::tlbf;
+
+tlbf!(
+    pub Large: u64 {
+        F00, F01, F02, F03, F04, F05, F06, F07, F08, F09, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, F25, F26, F27, F28, F29, F30, F31, F32, F33, F34, F35, F36, F37, F38, F39, F40, F41, F42, F43, F44, F45, F46, F47, F48, F49, F50, F51, F52, F53, F54, F55, F56, F57, F58, F59,
+    }
+);
+
+fn linear_scan(name: &str) -> Option<Large> {
+    Large::names()
+        .iter()
+        .position(|candidate| *candidate == name)
+        .map(|index| Large::all_flags()[index])
+}
+
+fn time(label: &str, rounds: u32, lookup: impl Fn(&str) -> Option<Large>) {
+    let start = Instant::now();
+    for _ in 0..rounds {
+        for name in Large::names() {
+            black_box(lookup(black_box(name)));
+        }
+    }
+    let elapsed = start.elapsed();
+    let lookups = rounds as u128 * Large::COUNT as u128;
+    println!("{label:>12}: {:?} total, {} ns per lookup", elapsed, elapsed.as_nanos() / lookups);
+}
+
+fn main() {
+    for name in Large::names() {
+        assert_eq!(Large::from_name(name), linear_scan(name));
+    }
+    let rounds = 200_000;
+    time("from_name", rounds, Large::from_name);
+    time("linear scan", rounds, linear_scan);
+}
@@ -13,6 +13,22 @@ impl<A: SetMember + Default, B: SetMember<Set = A::Set> + Default> Or<A, B>  {
     pub fn equals(&self, other: impl SetMember<Set=Self>) -> bool {
         other.eq_set(self)
     }
+
+    /// Materialize the combinator into a concrete set of flags.
+    ///
+    /// ```
+    /// # use tlbf::*;
+    /// # tlbf!(
+    /// #     pub Color: u64 {
+    /// #         pub Red, Green, Blue,
+    /// #     }
+    /// # );
+    /// let flags = Or::<Red, Blue>::default().to_flags();
+    /// assert_eq!(flags, Color::Red | Color::Blue);
+    /// ```
+    pub fn to_flags(&self) -> A::Set {
+        self.to_set()
+    }
 }
 
 impl<A: SetMember + Default, B: SetMember<Set = A::Set> + Default> SetMember for Or<A, B>  {
@@ -31,310 +47,4816 @@ impl<A: SetMember + Default, B: SetMember<Set = A::Set> + Default> SetMember for
     }
 }
 
+/// Type level combinator requiring both members.
+///
+/// Unlike [`Or`], which is in a set if either member is, `And` is in a set
+/// only if both members are. Both convert to the union of their members.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// assert!((Red | Blue).contains(And::<Red, Blue>::default()));
+/// assert!(!(Red | Green).contains(And::<Red, Blue>::default()));
+/// assert!((Red | Green).contains(Or::<Red, Blue>::default()));
+/// ```
+#[ghost::phantom]
+#[derive(Debug, Default)]
+pub struct And<A, B>;
+
+impl<A: SetMember + Default, B: SetMember<Set = A::Set> + Default> SetMember for And<A, B>  {
+    type Set = A::Set;
+
+    fn to_set(&self) -> Self::Set {
+        A::default_set() | B::default_set()
+    }
+
+    fn eq_set(&self, set: &Self::Set) -> bool {
+        &Self::default_set() == set
+    }
+
+    fn in_set(&self, set: &Self::Set) -> bool {
+        A::default().in_set(set) && B::default().in_set(set)
+    }
+}
+
+/// Member contributing no flags to the set `S`, the neutral element of `|`.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// assert_eq!(Empty::<Color>::default().to_set(), Color::EMPTY);
+/// assert_eq!(Red | Empty::<Color>::default(), Color::Red);
+/// ```
+#[ghost::phantom]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Empty<S>;
+
+impl<S: Flags> SetMember for Empty<S> {
+    type Set = S;
+
+    fn to_set(&self) -> Self::Set {
+        S::EMPTY
+    }
+
+    fn eq_set(&self, set: &Self::Set) -> bool {
+        set == &S::EMPTY
+    }
+
+    fn in_set(&self, _: &Self::Set) -> bool {
+        true
+    }
+}
+
+/// Member `T`, included only if the condition holds.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// let warm = true;
+/// assert_eq!(Blue.and_set(When::<Red>::new(warm)), Red | Blue);
+/// assert_eq!(Blue.and_set(When::<Red>::new(!warm)), Color::Blue);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct When<T>(pub bool, pub core::marker::PhantomData<T>);
+
+impl<T> When<T> {
+    pub const fn new(condition: bool) -> Self {
+        When(condition, core::marker::PhantomData)
+    }
+}
+
+impl<T: SetMember + Default> SetMember for When<T> where T::Set: Flags {
+    type Set = T::Set;
+
+    fn to_set(&self) -> Self::Set {
+        if self.0 {
+            T::default_set()
+        } else {
+            Flags::EMPTY
+        }
+    }
+
+    fn eq_set(&self, set: &Self::Set) -> bool {
+        &self.to_set() == set
+    }
+
+    fn in_set(&self, set: &Self::Set) -> bool {
+        !self.0 || T::default().in_set(set)
+    }
+}
+
 /// Member of a set of flags.
 pub trait SetMember: Sized{
     type Set: PartialEq + core::ops::BitOr<Self::Set, Output = Self::Set>;
     fn to_set(&self) -> Self::Set;
     fn eq_set(&self, set: &Self::Set) -> bool;
     fn in_set(&self, set: &Self::Set) -> bool;
+    /// Returns true if `self` is contained in `set`, the same as
+    /// [`SetMember::in_set`].
+    ///
+    /// Reads as `member.contained_in(set)`, the reverse of `set.contains(member)`.
+    fn contained_in(&self, set: &Self::Set) -> bool {
+        self.in_set(set)
+    }
+    /// Borrows `self` as a set if it is one, letting generic code avoid
+    /// [`SetMember::to_set`]. Returns `None` by default.
+    fn as_set(&self) -> Option<&Self::Set> {
+        None
+    }
     fn and_set(self, other: impl SetMember<Set = Self::Set>) -> Self::Set {
         self.to_set() | other.to_set()
     }
     fn default_set() -> Self::Set where Self: Default {
         Self::to_set(&Default::default())
     }
+    /// Raw bits of [`SetMember::to_set`], widened to `u128`.
+    fn set_bits(&self) -> u128 where Self::Set: Bits {
+        self.to_set().raw()
+    }
+    /// Returns true if [`SetMember::to_set`] has no flags set.
+    fn is_empty_set(&self) -> bool where Self::Set: Flags {
+        self.to_set() == Flags::EMPTY
+    }
 }
 
-/// Type level bitflags.
-/// 
-/// # Example
-/// 
-/// ```
-/// # use tlbf::*;
-/// tlbf!(
-///     pub Color: u64 {
-///         Red,
-///         Green, 
-///         Blue,
-///     }
-/// );
-/// assert!(Color::Red.contains(Red));
-/// assert!(!Color::Red.contains(Green));
-/// assert!(Red|Green == Color::Red|Color::Green);
-/// assert!((Red|Green).contains(Red));
-/// assert!((Red|Green).contains(Green));
-/// assert!(!(Red|Green).contains(Blue));
-/// ```
-#[macro_export]
-macro_rules! tlbf {
-    (
-        $(#[$($flags_args: tt)*])*
-        $vis: vis $flags_name: ident: $repr: ty {
-            $(
-                $(#[$($branch_args: tt)*])*
-                $vis2: vis $name: ident
-            ),* $(,)?
-        }
-    ) => {
-        $crate::tlbf! (
-            $(#[$($flags_args)*])*
-            $vis $flags_name: $repr {
-                $(
-                    $(#[$($branch_args)*])*
-                    $vis2 $name
-                ),*
+impl<T: SetMember> SetMember for &T {
+    type Set = T::Set;
+
+    fn to_set(&self) -> Self::Set {
+        T::to_set(self)
+    }
+
+    fn eq_set(&self, set: &Self::Set) -> bool {
+        T::eq_set(self, set)
+    }
+
+    fn in_set(&self, set: &Self::Set) -> bool {
+        T::in_set(self, set)
+    }
+
+    fn as_set(&self) -> Option<&Self::Set> {
+        T::as_set(self)
+    }
+}
+
+/// Access to the raw bits of a set of flags, widened to `u128`.
+pub trait Bits {
+    fn raw(&self) -> u128;
+}
+
+/// Integer types usable as the representation of flags.
+///
+/// Generated code delegates formatting and parsing to functions generic over
+/// this trait, so flag types sharing a representation share that code.
+pub trait FlagRepr:
+    Copy
+    + Eq
+    + core::fmt::LowerHex
+    + core::ops::BitAnd<Output = Self>
+    + core::ops::BitOr<Output = Self>
+    + core::ops::Not<Output = Self>
+{
+    const ZERO: Self;
+    const BITS: u32;
+    fn count_ones(self) -> u32;
+    /// Parses a bit pattern in `radix` as the unsigned integer of the same width,
+    /// so signed types accept values with the sign bit set.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError>;
+}
+
+macro_rules! impl_flag_repr {
+    ($($ty: ty => $unsigned: ty),*) => {
+        $(impl FlagRepr for $ty {
+            const ZERO: Self = 0;
+            const BITS: u32 = <$ty>::BITS;
+
+            #[inline]
+            fn count_ones(self) -> u32 {
+                <$ty>::count_ones(self)
             }
-            {} (0)
-        );
-    };
-    (
-        $(#[$($flags_args: tt)*])*
-        $vis: vis $flags_name: ident: $repr: ty {
-            $(#[$($first_args: tt)*])*
-            $vis0: vis $first: ident
-            $(  
-                ,$(#[$($branch_args: tt)*])*
-                $vis2: vis $name: ident
-            )* $(,)?
-        }
-        {$($(#[$($a: tt)*])* $v: vis $x: ident = $y: expr),*} ($value: expr)
-    ) => {
-        $crate::tlbf! (
-            $(#[$($flags_args)*])*
-            $vis $flags_name: $repr {
-                $(
-                    $(#[$($branch_args)*])*
-                    $vis2 $name
-                ),*
+
+            #[inline]
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError> {
+                <$unsigned>::from_str_radix(s, radix).map(|bits| bits as $ty)
             }
-            {
-                $($(#[$($a)*])* $v $x = $y,)* 
-                $(#[$($first_args)*])*
-                $vis0 $first = $value
-            } ($value + 1)
-        );
-    };
-    (
-        $(#[$($flags_args: tt)*])*
-        $vis: vis $flags_name: ident: $repr: ty {$(,)?}
-        {$($(#[$($a: tt)*])* $vis2: vis $x: ident = $y: expr),*} ($value: expr)
-    ) => {
-        $crate::tlbf! (
-            $(#[$($flags_args)*])*
-            $vis $flags_name: $repr
-            {$($vis2 $x = $y),*}
-        );
+        })*
     };
-    (
-        $(#[$($flags_args: tt)*])*
-        $vis: vis $flags_name: ident: $repr: ty {
-            $(
-                $(#[$($branch_args: tt)*])*
-                $vis2: vis $name: ident = $value: expr
-            ),* $(,)?
-        }
-    ) => {
-        #[repr(transparent)]
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-        $(#[$($flags_args)*])*
-        $vis struct $flags_name($repr);
+}
 
-        const _: () = {
-            #[allow(non_upper_case_globals)]
-            impl $flags_name {
-                $($vis const $name: Self = Self(1 << ($value));)*
+impl_flag_repr!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
 
-                pub fn is_empty(&self) -> bool {
-                    self.0 == 0
-                }
+/// Error returned when parsing flags from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// A number could not be parsed.
+    InvalidNumber,
+    /// A number contains undefined bits.
+    UndefinedBits,
+    /// A name does not match any flag.
+    UnknownName,
+    /// The input is not well formed.
+    InvalidSyntax,
+}
 
-                pub fn contains(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
-                    other.in_set(self)
-                }
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            ParseError::InvalidNumber => "invalid number",
+            ParseError::UndefinedBits => "number contains undefined bits",
+            ParseError::UnknownName => "unknown flag name",
+            ParseError::InvalidSyntax => "invalid syntax",
+        })
+    }
+}
 
-                pub fn equals(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
-                    other.eq_set(self)
-                }
+/// Error returned when exactly one flag is expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SingleFlagError {
+    /// No flags are set.
+    Empty,
+    /// More than one flag is set.
+    Multiple,
+}
 
-                pub fn intersects(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
-                    self.0 & other.to_set().0 > 0
-                }
+impl core::fmt::Display for SingleFlagError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            SingleFlagError::Empty => "no flags set",
+            SingleFlagError::Multiple => "multiple flags set",
+        })
+    }
+}
 
-                pub const fn all() -> Self {
-                    Self($(Self::$name.0)|*)
-                }
-            }
+/// Error returned by `try_insert` when a flag of the same exclusive group
+/// is already set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExclusivityError<S> {
+    /// The flags already set that conflict with the inserted member.
+    pub conflict: S,
+}
 
-            impl $crate::SetMember for $flags_name {
-                type Set = $flags_name;
-                fn to_set(&self) -> Self::Set {
-                    (*self).into()
-                }
-                fn eq_set(&self, set: &Self::Set) -> bool {
-                    self == set
-                }
-                fn in_set(&self, set: &Self::Set) -> bool {
-                    set.0 & self.0 == self.0
-                }
-            }
+impl<S: core::fmt::Debug> core::fmt::Display for ExclusivityError<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "conflicts with {:?} in an exclusive group", self.conflict)
+    }
+}
 
-            impl<T> ::core::ops::BitAnd<T> for $flags_name where T: $crate::SetMember<Set = Self> {
-                type Output = Self;
-                fn bitand(self, rhs: T) -> Self {
-                    Self(self.0 & rhs.to_set().0)
-                }
-            }
+/// A binary set operation, applied with `combine` on types generated by [`tlbf!`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetOp {
+    /// Flags in either set.
+    Union,
+    /// Flags in both sets.
+    Intersection,
+    /// Flags in the first set but not the second.
+    Difference,
+    /// Flags in exactly one of the sets.
+    SymmetricDifference,
+}
 
-            impl<T> ::core::ops::BitOr<T> for $flags_name where T: $crate::SetMember<Set = Self> {
-                type Output = Self;
-                fn bitor(self, rhs: T) -> Self {
-                    Self(self.0 | rhs.to_set().0)
-                }
-            }
+/// A concrete set of flags, implemented by types generated with [`tlbf!`].
+pub trait Flags: SetMember<Set = Self> + Copy + PartialEq + core::ops::BitOr<Self, Output = Self> {
+    /// The set with no flags.
+    const EMPTY: Self;
+}
 
-            impl<T> ::core::ops::BitXor<T> for $flags_name where T: $crate::SetMember<Set = Self> {
-                type Output = Self;
-                fn bitxor(self, rhs: T) -> Self {
-                    Self(self.0 ^ rhs.to_set().0)
-                }
-            }
+/// A condition evaluated against a set of flags at runtime.
+///
+/// Every [`SetMember`] is a predicate testing whether it is in the set.
+/// [`any_of`] and [`all_of`] combine predicates.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// // Either both Red and Green, or Blue.
+/// let rule = any_of([Red | Green, Color::Blue]);
+/// assert!(rule.test(&(Red | Green)));
+/// assert!(rule.test(&Color::Blue));
+/// assert!(!rule.test(&Color::Red));
+/// // Green together with either Red or Blue.
+/// let rule = any_of([all_of([Color::Red, Color::Green]), all_of([Color::Blue, Color::Green])]);
+/// assert!(rule.test(&(Blue | Green)));
+/// assert!(!rule.test(&(Red | Blue)));
+/// ```
+pub trait FlagPredicate<S> {
+    fn test(&self, set: &S) -> bool;
+}
 
-            impl<T> ::core::ops::BitAndAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
-                fn bitand_assign(&mut self, rhs: T) {
-                    self.0 &= rhs.to_set().0
-                }
+impl<T: SetMember> FlagPredicate<T::Set> for T {
+    fn test(&self, set: &T::Set) -> bool {
+        self.in_set(set)
+    }
+}
+
+/// Predicate holding if any of its predicates holds, see [`any_of`].
+#[derive(Debug, Clone, Copy)]
+pub struct AnyOf<P, const N: usize>(pub [P; N]);
+
+/// Predicate holding if all of its predicates hold, see [`all_of`].
+#[derive(Debug, Clone, Copy)]
+pub struct AllOf<P, const N: usize>(pub [P; N]);
+
+/// Predicate holding if any of `predicates` holds, false if there are none.
+pub fn any_of<P, const N: usize>(predicates: [P; N]) -> AnyOf<P, N> {
+    AnyOf(predicates)
+}
+
+/// Predicate holding if all of `predicates` hold, true if there are none.
+pub fn all_of<P, const N: usize>(predicates: [P; N]) -> AllOf<P, N> {
+    AllOf(predicates)
+}
+
+impl<S, P: FlagPredicate<S>, const N: usize> FlagPredicate<S> for AnyOf<P, N> {
+    fn test(&self, set: &S) -> bool {
+        self.0.iter().any(|predicate| predicate.test(set))
+    }
+}
+
+impl<S, P: FlagPredicate<S>, const N: usize> FlagPredicate<S> for AllOf<P, N> {
+    fn test(&self, set: &S) -> bool {
+        self.0.iter().all(|predicate| predicate.test(set))
+    }
+}
+
+/// Extension methods for iterators over [`SetMember`]s.
+pub trait IterFlagsExt: Iterator {
+    /// Union of every member in the iterator.
+    ///
+    /// ```
+    /// # use tlbf::*;
+    /// # tlbf!(
+    /// #     pub Color: u64 {
+    /// #         pub Red, Green, Blue,
+    /// #     }
+    /// # );
+    /// assert_eq!([Red, Red].into_iter().fold_flags(), Color::Red);
+    /// assert_eq!(core::iter::empty::<Red>().fold_flags(), Color::EMPTY);
+    /// ```
+    fn fold_flags(self) -> <Self::Item as SetMember>::Set
+    where
+        Self: Sized,
+        Self::Item: SetMember,
+        <Self::Item as SetMember>::Set: Flags,
+    {
+        self.fold(Flags::EMPTY, |set, member| set | member.to_set())
+    }
+}
+
+impl<I: Iterator> IterFlagsExt for I {}
+
+macro_rules! impl_tuple_set_member {
+    () => {};
+    ($first: ident $($name: ident)*) => {
+        /// Requires every member of the tuple to be present.
+        #[allow(non_snake_case)]
+        impl<$first: SetMember, $($name: SetMember<Set = $first::Set>),*> SetMember for ($first, $($name,)*) {
+            type Set = $first::Set;
+
+            fn to_set(&self) -> Self::Set {
+                let ($first, $($name,)*) = self;
+                $first.to_set() $(| $name.to_set())*
             }
 
-            impl<T> ::core::ops::BitOrAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
-                fn bitor_assign(&mut self, rhs: T) {
-                    self.0 |= rhs.to_set().0
-                }
+            fn eq_set(&self, set: &Self::Set) -> bool {
+                &self.to_set() == set
             }
 
-            impl<T> ::core::ops::BitXorAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
-                fn bitxor_assign(&mut self, rhs: T) {
-                    self.0 ^= rhs.to_set().0
-                }
+            fn in_set(&self, set: &Self::Set) -> bool {
+                let ($first, $($name,)*) = self;
+                $first.in_set(set) $(&& $name.in_set(set))*
             }
-        };
+        }
 
+        impl_tuple_set_member!($($name)*);
+    };
+}
 
-        $(
-            $(#[$($branch_args)*])*
-            #[derive(Debug, Default, Clone, Copy, Eq, Hash)]
-            $vis2 struct $name;
+impl_tuple_set_member!(A B C D E F G H I J K L);
+
+/// Type level bitflags.
+/// 
+/// # Example
+/// 
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Color: u64 {
+///         Red,
+///         Green, 
+///         Blue,
+///     }
+/// );
+/// assert!(Color::Red.contains(Red));
+/// assert!(!Color::Red.contains(Green));
+/// assert!(Red|Green == Color::Red|Color::Green);
+/// assert!((Red|Green).contains(Red));
+/// assert!((Red|Green).contains(Green));
+/// assert!(!(Red|Green).contains(Blue));
+/// ```
+///
+/// # Explicit bit indices
+///
+/// `Name = index` assigns a bit index explicitly, and following flags
+/// continue counting from it. An index that does not fit in the
+/// representation fails to compile, as do two flags sharing a bit; the error
+/// names both flags.
+///
+/// ```compile_fail
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Color: u8 {
+///         Red,
+///         Green = 8,
+///     }
+/// );
+/// ```
+///
+/// ```compile_fail
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Color: u8 {
+///         Red = 2,
+///         Green = 0,
+///         Blue,
+///         Yellow,
+///     }
+/// );
+/// ```
+///
+/// # Conditional flags
+///
+/// `#[cfg(..)]` on an entry removes the flag and everything generated for it.
+/// A flag that is configured out still consumes its bit index, so the bits of
+/// the remaining flags do not depend on the active configuration.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Color: u64 {
+///         Red,
+///         #[cfg(any())]
+///         Green,
+///         Blue,
+///     }
+/// );
+/// assert_eq!(Color::Blue, Color::from(Blue));
+/// assert_eq!(Color::all_flags(), &[Color::Red, Color::Blue]);
+/// ```
+///
+/// # Renaming
+///
+/// `#[tlbf(rename = "..")]` on an entry changes the name used by `Display`,
+/// `names`, `name` and `from_name`. The Rust identifier is used by default.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Permission: u8 {
+///         Read,
+///         #[tlbf(rename = "read-write")]
+///         ReadWrite,
+///     }
+/// );
+/// assert_eq!(Permission::names(), &["Read", "read-write"]);
+/// assert_eq!(Permission::from_name("read-write"), Some(Permission::ReadWrite));
+/// assert_eq!(Permission::from_name("ReadWrite"), None);
+/// ```
+///
+/// # Deprecated flags
+///
+/// `#[deprecated]` on a flag applies to both its type and its constant. With
+/// `#[tlbf(skip_deprecated)]`, deprecated flags are also left out of `all()`,
+/// `ALL`, `MASK`, `COUNT`, `all_flags`, `names` and `metadata`, so iteration and
+/// formatting ignore them, but `from_bits` and `from_name` still accept them.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     #[tlbf(skip_deprecated)]
+///     pub Permission: u8 {
+///         Read,
+///         #[deprecated = "use `Read`"]
+///         Legacy,
+///         Write,
+///     }
+/// );
+/// assert_eq!(Permission::names(), ["Read", "Write"]);
+/// assert_eq!(Permission::ALL, Read | Write);
+/// assert!(Permission::from_bits(0b010).is_some());
+/// ```
+///
+/// Deprecated flags still may not share a bit with another flag.
+///
+/// ```compile_fail
+/// # use tlbf::*;
+/// tlbf!(
+///     #[tlbf(skip_deprecated)]
+///     pub Permission: u8 {
+///         Read,
+///         #[deprecated]
+///         Legacy = 0,
+///         Write = 1,
+///     }
+/// );
+/// ```
+///
+/// # Aliases
+///
+/// `const` entries name a combination of flags. Aliases do not consume a bit
+/// and are not counted by `all_flags`, `names` or `COUNT`.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Permission: u8 {
+///         Read,
+///         Write,
+///         pub const ReadWrite = Read | Write,
+///         Execute,
+///     }
+/// );
+/// assert_eq!(Permission::ReadWrite, Read | Write);
+/// assert_eq!(Permission::Execute, Permission::from_bits_retain(0b100));
+/// assert_eq!(Permission::COUNT, 3);
+/// ```
+///
+/// `bits(lo..hi)` names the contiguous range of bits `lo` to `hi`, exclusive,
+/// whether or not flags occupy them.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Register: u16 {
+///         Enable,
+///         Ready,
+///         pub const Reserved = bits(8..16),
+///     }
+/// );
+/// assert_eq!(Register::Reserved.bits(), 0xff00);
+/// assert_eq!(Register::COUNT, 2);
+/// ```
+///
+/// # Exclusive groups
+///
+/// Groups of mutually exclusive flags can be declared after the flags.
+/// Each group becomes a mask constant, and `set_exclusive` clears the
+/// rest of a group before setting one of its members.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub Style: u8 {
+///         Red,
+///         Green,
+///         Blue,
+///         Bold,
+///     }
+///     exclusive Color { Red, Green, Blue }
+/// );
+/// let mut style = Style::Red | Style::Bold;
+/// style.set_exclusive(Blue);
+/// assert_eq!(style, Blue | Bold);
+/// assert_eq!(Style::Color, Red | Green | Blue);
+/// ```
+///
+/// # Attributes
+///
+/// Attributes on the flags type, such as `#[non_exhaustive]`, are placed on
+/// the generated struct. `#[non_exhaustive]` keeps other crates from constructing
+/// it directly, but flags added later are still new bits to them: `from_bits`
+/// rejects bits unknown to the version compiled against, while
+/// `from_bits_retain` keeps them.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     #[non_exhaustive]
+///     pub Color: u8 {
+///         Red,
+///         Green,
+///     }
+/// );
+/// assert_eq!(Color::from_bits(0b100), None);
+/// assert_eq!(Color::from_bits_retain(0b100).bits(), 0b100);
+/// ```
+///
+/// # Modules
+///
+/// Every flag is an item in the calling scope, so two flags types sharing a
+/// flag name collide. `mod name { .. }` places everything generated in a new
+/// module, which can see the items of its parent.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub mod light {
+///         pub Light: u8 {
+///             pub Red,
+///             pub Green,
+///         }
+///     }
+/// );
+/// tlbf!(
+///     pub mod paint {
+///         pub Paint: u8 {
+///             pub Red,
+///             pub Blue,
+///         }
+///     }
+/// );
+/// assert_eq!(light::Light::Red | light::Green, light::Light::ALL);
+/// assert_eq!(paint::Red.to_set(), paint::Paint::Red);
+/// ```
+///
+/// # Options
+///
+/// Generated code can be customized with `#[tlbf(..)]` attributes on the
+/// flags type, one option per attribute.
+///
+/// * `#[tlbf(default = ..)]`: implement `Default` with the given members.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     #[tlbf(default = Read | Write)]
+///     pub Permission: u8 {
+///         Read,
+///         Write,
+///         Execute,
+///     }
+/// );
+/// assert_eq!(Permission::default(), Read | Write);
+/// ```
+///
+/// * `#[tlbf(transparent_ord)]`: implement `Ord` and `PartialOrd` by comparing
+///   the bits numerically, consistent with `Eq`.
+/// * `#[tlbf(subset_ord)]`: implement `PartialOrd` as the subset order, so `a < b`
+///   means `a` is a strict subset of `b` and `a >= b` means `a` contains `b`.
+///   Sets where neither contains the other are incomparable: `partial_cmp`
+///   returns `None` and every comparison operator returns false. `Ord` is not
+///   implemented, so such types cannot be sorted. Cannot be combined with
+///   `transparent_ord`.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     #[tlbf(subset_ord)]
+///     pub Permission: u8 {
+///         Read,
+///         Write,
+///         Execute,
+///     }
+/// );
+/// assert!(Permission::Read < Read | Write);
+/// assert!(Permission::ALL >= Read | Execute);
+/// assert!(!(Permission::Read < Permission::Write));
+/// assert!(!(Permission::Read >= Permission::Write));
+/// assert_eq!(Permission::Read.partial_cmp(&Permission::Write), None);
+/// ```
+/// * `#[tlbf(ignore_reserved_bits)]`: `PartialEq`, `Eq` and `Hash` only consider
+///   defined bits, so values differing only in undefined bits are equal. By
+///   default these compare the raw bits. This also applies to comparisons with
+///   the underlying integer. Constants of such a type cannot be used
+///   as patterns.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     #[tlbf(ignore_reserved_bits)]
+///     pub Register: u8 {
+///         Enable,
+///         Ready,
+///     }
+/// );
+/// assert_eq!(Register::from_bits_retain(0b1001), Register::Enable);
+/// ```
+/// * `#[tlbf(widen = path::to::Flags)]`: convert into another `tlbf!` type with
+///   an equal or wider representation, keeping every bit at its position. Fails to
+///   compile unless each flag exists in the other type under the same name and bit.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     pub mod v2 {
+///         pub Color: u16 {
+///             pub Red,
+///             pub Green,
+///             pub Blue,
+///             pub Alpha,
+///         }
+///     }
+/// );
+/// tlbf!(
+///     #[tlbf(widen = v2::Color)]
+///     pub Color: u8 {
+///         Red,
+///         Green,
+///         Blue,
+///     }
+/// );
+/// assert_eq!(v2::Color::from(Red | Blue), v2::Red | v2::Blue);
+/// ```
+///
+/// * `#[tlbf(add_ops)]`: implement `Add` and `AddAssign` as union, the same
+///   as `BitOr` and `BitOrAssign`.
+/// * `#[tlbf(kind = ColorKind)]`: generate an enum with a variant for each flag,
+///   convertible into the flags type, and `as_kind` returning the variant if
+///   exactly one flag is set, for exhaustive `match`.
+///
+/// ```
+/// # use tlbf::*;
+/// tlbf!(
+///     #[tlbf(kind = ColorKind)]
+///     pub Color: u8 {
+///         Red,
+///         Green,
+///     }
+/// );
+/// let name = match Color::Green.as_kind() {
+///     Some(ColorKind::Red) => "red",
+///     Some(ColorKind::Green) => "green",
+///     None => "none or mixed",
+/// };
+/// assert_eq!(name, "green");
+/// assert_eq!(Color::from(ColorKind::Red), Color::Red);
+/// ```
+///
+/// * `#[tlbf(expose_raw)]`: add `bits_mut`, giving mutable access to the
+///   underlying integer. Undefined bits can be set through it; call
+///   `normalized` afterwards if they must not persist.
+/// * `#[tlbf(debug_hex)]`, `#[tlbf(debug_names)]`: `Debug` shows the bits in
+///   hex as `Color(0x05)`, or the flag names as `Color(Red | Green)`. By default
+///   `Debug` shows the bits in decimal as `Color(5)`.
+/// * `#[tlbf(display_sep = ", ")]`: separator between flag names used by
+///   `Display` and `FromStr`, `" | "` by default.
+/// * `#[tlbf(compat = path::to::Flags)]`: convert to and from another flags type
+///   with the same bit layout, such as one generated by `bitflags`. The other type
+///   must provide `bits()` and `from_bits_retain()`.
+/// * `#[tlbf(test)]`: generate a `#[cfg(test)]` module `tlbf_tests` checking that
+///   `all()` contains every flag and that every flag, `ALL` and `EMPTY` round-trip
+///   through `from_bits` and through `Display` and `FromStr`. Use
+///   `#[tlbf(test = module_name)]` to name the module, e.g. when several types
+///   in the same module use this option. The type must be declared at module
+///   level, not inside a function.
+///
+/// # Cargo features
+///
+/// * `alloc`: implement `TryFrom<&[String]>` and add `to_json_array` and
+///   `from_json_array`, converting to and from a JSON array of flag names.
+/// * `borsh`: implement `BorshSerialize` and `BorshDeserialize` as the
+///   underlying representation. Undefined bits are rejected when deserializing.
+/// * `serde`: implement `Serialize` and `Deserialize`, as the `Display` string
+///   in human readable formats and as the underlying representation otherwise.
+///   Undefined bits are rejected when deserializing. `#[serde(default)]` on a
+///   field of a flags type uses `#[tlbf(default = ..)]` when the field is absent.
+#[macro_export]
+macro_rules! tlbf {
+    ($(#[$($mod_args: tt)*])* $vis: vis mod $mod_name: ident {$($inner: tt)*}) => {
+        $(#[$($mod_args)*])*
+        $vis mod $mod_name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::tlbf!($($inner)*);
+        }
+    };
+    (
+        $(#[$($flags_args: tt)*])*
+        $vis: vis $flags_name: ident: $repr: ty {
+            $($body: tt)*
+        }
+        $(exclusive $group: ident {$($member: ident),* $(,)?})*
+    ) => {
+        $crate::tlbf! (@options [] []
+            $(#[$($flags_args)*])*
+            $vis $flags_name: $repr {$($body)*}
+            $(exclusive $group {$($member),*})*
+        );
+    };
+    (@options [$($opts: tt)*] [$($attrs: tt)*] #[tlbf($($opt: tt)*)] $($rest: tt)*) => {
+        $crate::tlbf! (@options [$($opts)* ($($opt)*)] [$($attrs)*] $($rest)*);
+    };
+    (@options [$($opts: tt)*] [$($attrs: tt)*] #[$($attr: tt)*] $($rest: tt)*) => {
+        $crate::tlbf! (@options [$($opts)*] [$($attrs)* #[$($attr)*]] $($rest)*);
+    };
+    (@options [$($opts: tt)*] [$($attrs: tt)*] $vis: vis $flags_name: ident: $repr: ty {$($body: tt)*}
+        $(exclusive $group: ident {$($member: ident),*})*
+    ) => {
+        $crate::tlbf! (@deprecated [$($opts)*]
+            [[$($opts)*] $($attrs)* $vis $flags_name: $repr [$($group {$($member),*})*]]
+            $($body)*
+        );
+    };
+    (@deprecated [] $head: tt $($body: tt)*) => {
+        $crate::tlbf! (@munch (all()) $head {} {} (0) [] [] [] [] [] $($body)*);
+    };
+    (@deprecated [(skip_deprecated) $($opts: tt)*] $head: tt $($body: tt)*) => {
+        $crate::tlbf! (@munch (any()) $head {} {} (0) [] [] [] [] [] $($body)*);
+    };
+    (@deprecated [$opt: tt $($opts: tt)*] $head: tt $($body: tt)*) => {
+        $crate::tlbf! (@deprecated [$($opts)*] $head $($body)*);
+    };
+    (@option $ctx: tt) => {};
+    (@option [$flags_name: ident: $repr: ty] (default = $($value: tt)*) $($rest: tt)*) => {
+        impl ::core::default::Default for $flags_name {
+            #[inline]
+            fn default() -> Self {
+                $crate::SetMember::to_set(&($($value)*))
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (subset_ord) $($rest: tt)*) => {
+        impl ::core::cmp::PartialOrd for $flags_name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                let (lhs, rhs) = (self.__eq_bits(), other.__eq_bits());
+                if lhs == rhs {
+                    ::core::option::Option::Some(::core::cmp::Ordering::Equal)
+                } else if lhs & rhs == lhs {
+                    ::core::option::Option::Some(::core::cmp::Ordering::Less)
+                } else if lhs & rhs == rhs {
+                    ::core::option::Option::Some(::core::cmp::Ordering::Greater)
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (transparent_ord) $($rest: tt)*) => {
+        impl ::core::cmp::PartialOrd for $flags_name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl ::core::cmp::Ord for $flags_name {
+            #[inline]
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(&self.__eq_bits(), &other.__eq_bits())
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (compat = $compat: ty) $($rest: tt)*) => {
+        impl ::core::convert::From<$compat> for $flags_name {
+            #[inline]
+            fn from(flags: $compat) -> Self {
+                Self(flags.bits())
+            }
+        }
+
+        impl ::core::convert::From<$flags_name> for $compat {
+            #[inline]
+            fn from(flags: $flags_name) -> Self {
+                <$compat>::from_bits_retain(flags.0)
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option $ctx: tt (display_sep = $sep: literal) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (widen = $wide: ty) $($rest: tt)*) => {
+        impl ::core::convert::From<$flags_name> for $wide {
+            #[inline]
+            fn from(flags: $flags_name) -> Self {
+                let mut bits = flags.0;
+                let mut result = <$wide>::from_bits_retain(0);
+                while bits != 0 {
+                    let index = bits.trailing_zeros();
+                    bits &= !(1 << index);
+                    result = <$wide>::from_bits_retain(result.bits() | <$wide>::from_index(index).bits());
+                }
+                result
+            }
+        }
+
+        const _: () = $crate::__private::assert_widens(
+            $flags_name::metadata(),
+            $flags_name::REPR_BITS,
+            <$wide>::metadata(),
+            <$wide>::REPR_BITS,
+        );
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (add_ops) $($rest: tt)*) => {
+        impl<T> ::core::ops::Add<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: T) -> Self {
+                ::core::ops::BitOr::bitor(self, rhs)
+            }
+        }
+
+        impl<T> ::core::ops::AddAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+            #[inline]
+            fn add_assign(&mut self, rhs: T) {
+                ::core::ops::BitOrAssign::bitor_assign(self, rhs)
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (expose_raw) $($rest: tt)*) => {
+        impl $flags_name {
+            /// Mutable access to the underlying bits, which may be left with
+            /// undefined bits set.
+            #[inline]
+            pub fn bits_mut(&mut self) -> &mut $repr {
+                &mut self.0
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option $ctx: tt (test) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx (test = tlbf_tests) $($rest)*);
+    };
+    (@option [$flags_name: ident: $repr: ty] (test = $module: ident) $($rest: tt)*) => {
+        #[cfg(test)]
+        mod $module {
+            // Tests always link `std`, even for `no_std` crates.
+            extern crate std;
+            use std::string::ToString;
+            use super::$flags_name;
+
+            #[test]
+            fn all_contains_each_flag() {
+                for flag in $flags_name::ALL_FLAGS {
+                    assert!($flags_name::all().contains(flag), "{:?} is not in all()", flag);
+                }
+            }
+
+            #[test]
+            fn bits_round_trip() {
+                for flag in $flags_name::ALL_FLAGS {
+                    assert_eq!($flags_name::from_bits(flag.bits()), Some(flag));
+                }
+                assert_eq!($flags_name::from_bits($flags_name::ALL.bits()), Some($flags_name::ALL));
+                assert_eq!($flags_name::from_bits($flags_name::EMPTY.bits()), Some($flags_name::EMPTY));
+            }
+
+            #[test]
+            fn display_round_trip() {
+                for flag in $flags_name::ALL_FLAGS {
+                    assert_eq!(flag.to_string().parse(), Ok(flag));
+                }
+                assert_eq!($flags_name::ALL.to_string().parse(), Ok($flags_name::ALL));
+                assert_eq!($flags_name::EMPTY.to_string().parse(), Ok($flags_name::EMPTY));
+            }
+        }
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($rest)*);
+    };
+    (@option $ctx: tt (debug_hex) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx $($rest)*);
+    };
+    (@option $ctx: tt (debug_names) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx $($rest)*);
+    };
+    (@option $ctx: tt (kind = $kind: ident) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx $($rest)*);
+    };
+    (@option $ctx: tt (skip_deprecated) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx $($rest)*);
+    };
+    (@option $ctx: tt (ignore_reserved_bits) $($rest: tt)*) => {
+        $crate::tlbf! (@option $ctx $($rest)*);
+    };
+    (@option $ctx: tt ($($opt: tt)*) $($rest: tt)*) => {
+        ::core::compile_error!(::core::concat!("unknown tlbf option: ", ::core::stringify!($($opt)*)));
+    };
+    // Doc comments are taken together with the attribute or entry following
+    // them, and runs of plain flags four at a time, so long documented flag
+    // lists stay well within the recursion limit.
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [] [] [] [] []
+        $(#[doc $($doc0: tt)*])* $vis0: vis $name0: ident,
+        $(#[doc $($doc1: tt)*])* $vis1: vis $name1: ident,
+        $(#[doc $($doc2: tt)*])* $vis2: vis $name2: ident,
+        $(#[doc $($doc3: tt)*])* $vis3: vis $name3: ident,
+        $($rest: tt)*
+    ) => {
+        $crate::tlbf! (@munch $mode $head
+            {
+                $($done)*
+                [] [] [] [$(#[doc $($doc0)*])*] [] $vis0 $name0 = $value,
+                [] [] [] [$(#[doc $($doc1)*])*] [] $vis1 $name1 = $value + 1,
+                [] [] [] [$(#[doc $($doc2)*])*] [] $vis2 $name2 = $value + 2,
+                [] [] [] [$(#[doc $($doc3)*])*] [] $vis3 $name3 = $value + 3,
+            }
+            {$($aliases)*}
+            ($value + 4) [] [] [] [] []
+            $($rest)*
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        $(#[doc $($doc: tt)*])* #[cfg $pred: tt] $($rest: tt)*
+    ) => {
+        $crate::tlbf! (@munch $mode $head {$($done)*} {$($aliases)*} ($value)
+            [$($cfg)* #[cfg $pred]] [$($hide)*] [$($dep)*] [$($attrs)* $(#[doc $($doc)*])*] [$($rename)*]
+            $($rest)*
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        $(#[doc $($doc: tt)*])* #[deprecated $($args: tt)*] $($rest: tt)*
+    ) => {
+        $crate::tlbf! (@munch $mode $head {$($done)*} {$($aliases)*} ($value)
+            [$($cfg)*] [#[cfg $mode]] [$($dep)* #[deprecated $($args)*]] [$($attrs)* $(#[doc $($doc)*])* #[deprecated $($args)*]] [$($rename)*]
+            $($rest)*
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        $(#[doc $($doc: tt)*])* #[tlbf(rename = $name: literal)] $($rest: tt)*
+    ) => {
+        $crate::tlbf! (@munch $mode $head {$($done)*} {$($aliases)*} ($value)
+            [$($cfg)*] [$($hide)*] [$($dep)*] [$($attrs)* $(#[doc $($doc)*])*] [$name]
+            $($rest)*
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        $(#[doc $($doc: tt)*])* #[tlbf($($opt: tt)*)] $($rest: tt)*
+    ) => {
+        ::core::compile_error!(::core::concat!("unknown tlbf flag option: ", ::core::stringify!($($opt)*)));
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] []
+        $(#[doc $($doc: tt)*])* $vis: vis const $name: ident = bits($lo: tt..$hi: tt) $(, $($rest: tt)*)?
+    ) => {
+        $crate::tlbf! (@munch $mode $head
+            {$($done)*}
+            {$($aliases)* [$($cfg)*] [$($attrs)* $(#[doc $($doc)*])*] $vis $name = ($lo..$hi),}
+            ($value) [] [] [] [] []
+            $($($rest)*)?
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] []
+        $(#[doc $($doc: tt)*])* $vis: vis const $name: ident = $($part: ident)|+ $(, $($rest: tt)*)?
+    ) => {
+        $crate::tlbf! (@munch $mode $head
+            {$($done)*}
+            {$($aliases)* [$($cfg)*] [$($attrs)* $(#[doc $($doc)*])*] $vis $name = [$($part)|+],}
+            ($value) [] [] [] [] []
+            $($($rest)*)?
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        $(#[doc $($doc: tt)*])* $vis: vis $name: ident = $index: expr $(, $($rest: tt)*)?
+    ) => {
+        $crate::tlbf! (@munch $mode $head
+            {$($done)* [$($cfg)*] [$($cfg)* $($hide)*] [$($dep)*] [$($attrs)* $(#[doc $($doc)*])*] [$($rename)*] $vis $name = $index,}
+            {$($aliases)*}
+            ($index + 1) [] [] [] [] []
+            $($($rest)*)?
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        $(#[doc $($doc: tt)*])* $vis: vis $name: ident $(, $($rest: tt)*)?
+    ) => {
+        $crate::tlbf! (@munch $mode $head
+            {$($done)* [$($cfg)*] [$($cfg)* $($hide)*] [$($dep)*] [$($attrs)* $(#[doc $($doc)*])*] [$($rename)*] $vis $name = $value,}
+            {$($aliases)*}
+            ($value + 1) [] [] [] [] []
+            $($($rest)*)?
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [$($cfg: tt)*] [$($hide: tt)*] [$($dep: tt)*] [$($attrs: tt)*] [$($rename: tt)*]
+        #[$($attr: tt)*] $($rest: tt)*
+    ) => {
+        $crate::tlbf! (@munch $mode $head {$($done)*} {$($aliases)*} ($value)
+            [$($cfg)*] [$($hide)*] [$($dep)*] [$($attrs)* #[$($attr)*]] [$($rename)*]
+            $($rest)*
+        );
+    };
+    (@munch $mode: tt $head: tt {$($done: tt)*} {$($aliases: tt)*} ($value: expr) [] [] [] [] []) => {
+        $crate::tlbf! (@impl $head {$($done)*} {$($aliases)*});
+    };
+    (@alias $repr: ty, [$($part: ident)|+]) => {
+        Self(0 $(| Self::$part.0)+)
+    };
+    (@alias $repr: ty, ($lo: tt..$hi: tt)) => {{
+        let mut bits: $repr = 0;
+        let mut index = $lo;
+        while index < $hi {
+            bits |= match (1 as $repr).checked_shl(index) {
+                Some(bit) => bit,
+                None => panic!("bit range does not fit in the representation"),
+            };
+            index += 1;
+        }
+        Self(bits)
+    }};
+    (@display $ctx: tt []) => {
+        $crate::tlbf! (@display $ctx " | ");
+    };
+    (@display $ctx: tt [(display_sep = $sep: literal) $($rest: tt)*]) => {
+        $crate::tlbf! (@display $ctx $sep);
+    };
+    (@display $ctx: tt [$opt: tt $($rest: tt)*]) => {
+        $crate::tlbf! (@display $ctx [$($rest)*]);
+    };
+    (@display [$flags_name: ident: $repr: ty] $sep: literal) => {
+        impl ::core::fmt::Display for $flags_name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                $crate::__private::fmt(self.0, Self::MASK, Self::metadata(), $sep, f)
+            }
+        }
+
+        impl ::core::str::FromStr for $flags_name {
+            type Err = $crate::ParseError;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::__private::from_str(Self::from_name_bits, $sep, s).map(Self)
+            }
+        }
+    };
+    (@debug $ctx: tt []) => {
+        $crate::tlbf! (@debug $ctx decimal);
+    };
+    (@debug $ctx: tt [(debug_hex) $($rest: tt)*]) => {
+        $crate::tlbf! (@debug $ctx hex);
+    };
+    (@debug $ctx: tt [(debug_names) $($rest: tt)*]) => {
+        $crate::tlbf! (@debug $ctx names);
+    };
+    (@debug $ctx: tt [$opt: tt $($rest: tt)*]) => {
+        $crate::tlbf! (@debug $ctx [$($rest)*]);
+    };
+    (@debug [$flags_name: ident: $repr: ty] decimal) => {
+        impl ::core::fmt::Debug for $flags_name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(::core::stringify!($flags_name)).field(&self.0).finish()
+            }
+        }
+    };
+    (@debug [$flags_name: ident: $repr: ty] hex) => {
+        impl ::core::fmt::Debug for $flags_name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let width = Self::REPR_BITS as usize / 4 + 2;
+                ::core::write!(f, "{}({:#0width$x})", ::core::stringify!($flags_name), self.0, width = width)
+            }
+        }
+    };
+    (@debug [$flags_name: ident: $repr: ty] names) => {
+        impl ::core::fmt::Debug for $flags_name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, "{}(", ::core::stringify!($flags_name))?;
+                if self.0 == 0 {
+                    f.write_str("0x0")?;
+                } else {
+                    $crate::__private::fmt(self.0, Self::MASK, Self::metadata(), " | ", f)?;
+                }
+                f.write_str(")")
+            }
+        }
+    };
+    (@kind $ctx: tt $flags: tt []) => {};
+    (@kind $ctx: tt $flags: tt [(kind = $kind: ident) $($rest: tt)*]) => {
+        $crate::tlbf! (@kind $ctx $flags $kind);
+    };
+    (@kind $ctx: tt $flags: tt [$opt: tt $($rest: tt)*]) => {
+        $crate::tlbf! (@kind $ctx $flags [$($rest)*]);
+    };
+    (@kind [$vis: vis $flags_name: ident: $repr: ty] [$($(#[$cfg: meta])* $name: ident)*] $kind: ident) => {
+        /// A single flag of
+        #[doc = ::core::concat!("[`", ::core::stringify!($flags_name), "`].")]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $kind {
+            $($(#[$cfg])* $name,)*
+        }
+
+        #[allow(deprecated)]
+        const _: () = {
+            impl $flags_name {
+                /// The set flag as a
+                #[doc = ::core::concat!("[`", ::core::stringify!($kind), "`],")]
+                /// if exactly one defined flag is set.
+                #[inline]
+                pub fn as_kind(&self) -> Option<$kind> {
+                    let bits = self.0 & Self::KNOWN_BITS;
+                    $($(#[$cfg])* if bits == Self::$name.0 {
+                        return Some($kind::$name);
+                    })*
+                    None
+                }
+            }
+
+            impl ::core::convert::From<$kind> for $flags_name {
+                #[inline]
+                fn from(kind: $kind) -> Self {
+                    match kind {
+                        $($(#[$cfg])* $kind::$name => Self::$name,)*
+                    }
+                }
+            }
+
+            impl $crate::SetMember for $kind {
+                type Set = $flags_name;
+                #[inline]
+                fn to_set(&self) -> Self::Set {
+                    (*self).into()
+                }
+                #[inline]
+                fn eq_set(&self, set: &Self::Set) -> bool {
+                    self.to_set() == *set
+                }
+                #[inline]
+                fn in_set(&self, set: &Self::Set) -> bool {
+                    set.contains_const(self.to_set())
+                }
+            }
+        };
+    };
+    (@struct $ctx: tt []) => {
+        $crate::tlbf! (@struct $ctx);
+    };
+    (@struct $ctx: tt [(ignore_reserved_bits) $($rest: tt)*]) => {
+        $crate::tlbf! (@struct $ctx ignore_reserved_bits);
+    };
+    (@struct $ctx: tt [$opt: tt $($rest: tt)*]) => {
+        $crate::tlbf! (@struct $ctx [$($rest)*]);
+    };
+    (@struct [$(#[$($flags_args: tt)*])* $vis: vis $flags_name: ident: $repr: ty]) => {
+        #[repr(transparent)]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        $(#[$($flags_args)*])*
+        $vis struct $flags_name($repr);
+
+        impl $flags_name {
+            #[doc(hidden)]
+            #[inline]
+            const fn __eq_bits(&self) -> $repr {
+                self.0
+            }
+
+            #[doc(hidden)]
+            const __IGNORES_RESERVED_BITS: bool = false;
+        }
+    };
+    (@struct [$(#[$($flags_args: tt)*])* $vis: vis $flags_name: ident: $repr: ty] ignore_reserved_bits) => {
+        #[repr(transparent)]
+        #[derive(Clone, Copy)]
+        $(#[$($flags_args)*])*
+        $vis struct $flags_name($repr);
+
+        impl $flags_name {
+            #[doc(hidden)]
+            #[inline]
+            const fn __eq_bits(&self) -> $repr {
+                self.0 & Self::MASK
+            }
+
+            #[doc(hidden)]
+            const __IGNORES_RESERVED_BITS: bool = true;
+        }
+
+        impl ::core::cmp::PartialEq for $flags_name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.__eq_bits() == other.__eq_bits()
+            }
+        }
+
+        impl ::core::cmp::Eq for $flags_name {}
+
+        impl ::core::hash::Hash for $flags_name {
+            #[inline]
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                self.__eq_bits().hash(state)
+            }
+        }
+    };
+    (@name $name: ident) => {
+        ::core::stringify!($name)
+    };
+    (@name $name: ident $rename: literal) => {
+        $rename
+    };
+    (@impl
+        [
+            [$($opts: tt)*] $(#[$($flags_args: tt)*])* $vis: vis $flags_name: ident: $repr: ty
+            [$($group: ident {$($member: ident),*})*]
+        ]
+        {$(
+            [$(#[$cfg: meta])*]
+            [$(#[$iter_cfg: meta])*]
+            [$(#[$($dep: tt)*])*]
+            [$(#[$($branch_args: tt)*])*]
+            [$($rename: literal)?]
+            $vis2: vis $name: ident = $value: expr,
+        )*}
+        {$(
+            [$(#[$alias_cfg: meta])*]
+            [$(#[$($alias_args: tt)*])*]
+            $alias_vis: vis $alias: ident = $alias_value: tt,
+        )*}
+    ) => {
+        $crate::tlbf! (@struct [$(#[$($flags_args)*])* $vis $flags_name: $repr] [$($opts)*]);
+
+        #[allow(deprecated)]
+        const _: () = {
+            #[allow(non_upper_case_globals)]
+            impl $flags_name {
+                $(
+                    $(#[$cfg])*
+                    $(#[$($dep)*])*
+                    $vis const $name: Self = match (1 as $repr).checked_shl(($value) as u32) {
+                        Some(bits) => Self(bits),
+                        None => ::core::panic!(::core::concat!(
+                            "bit index of `", ::core::stringify!($name),
+                            "` is out of range for `", ::core::stringify!($repr), "`"
+                        )),
+                    };
+                )*
+
+                $(
+                    $(#[$alias_cfg])*
+                    $(#[$($alias_args)*])*
+                    $alias_vis const $alias: Self = $crate::tlbf!(@alias $repr, $alias_value);
+                )*
+
+                $($vis const $group: Self = Self(0 $(| Self::$member.0)*);)*
+
+                /// Bits accepted by `from_bits`, including flags skipped by
+                /// `skip_deprecated`.
+                const KNOWN_BITS: $repr = {
+                    let mut bits = 0;
+                    $($(#[$cfg])* { bits |= Self::$name.0; })*
+                    bits
+                };
+
+                /// Bits accepted by the assign operators in debug builds, including
+                /// bits only covered by aliases such as `bits(lo..hi)`.
+                const ASSIGNABLE_BITS: $repr = {
+                    #[allow(unused_mut)]
+                    let mut bits = 0;
+                    $($(#[$alias_cfg])* { bits |= Self::$alias.0; })*
+                    bits | Self::KNOWN_BITS
+                };
+
+                /// No flags set.
+                pub const EMPTY: Self = Self(0);
+
+                /// Every defined flag set.
+                pub const ALL: Self = Self::all();
+
+                /// Bits of every defined flag.
+                pub const MASK: $repr = Self::all().0;
+
+                /// Number of defined flags.
+                pub const COUNT: usize = {
+                    let mut count = 0;
+                    $($(#[$iter_cfg])* { let _ = Self::$name; count += 1; })*
+                    count
+                };
+
+                /// Every defined flag, in declaration order.
+                pub const ALL_FLAGS: [Self; Self::COUNT] = [$($(#[$iter_cfg])* Self::$name),*];
+
+                /// Name and value of every defined flag, in declaration order.
+                pub const ENTRIES: [(&'static str, Self); Self::COUNT] = [
+                    $($(#[$iter_cfg])* ($crate::tlbf!(@name $name $($rename)?), Self::$name)),*
+                ];
+
+                /// Width of the underlying representation in bits.
+                pub const REPR_BITS: u32 = ::core::mem::size_of::<$repr>() as u32 * 8;
+
+                #[inline]
+                pub fn is_empty(&self) -> bool {
+                    self.0 == 0
+                }
+
+                /// Raw bits of the value.
+                #[inline]
+                pub const fn bits(&self) -> $repr {
+                    self.0
+                }
+
+                /// Returns `None` if `bits` contains undefined bits. Bits of flags skipped
+                /// by `skip_deprecated` are accepted.
+                #[inline]
+                pub const fn from_bits(bits: $repr) -> Option<Self> {
+                    if bits & Self::KNOWN_BITS == bits {
+                        Some(Self(bits))
+                    } else {
+                        None
+                    }
+                }
+
+                /// Discards undefined bits.
+                #[inline]
+                pub const fn from_bits_truncate(bits: $repr) -> Self {
+                    Self(bits & Self::KNOWN_BITS)
+                }
+
+                /// Single bit at raw bit position `index`, empty if `index` is out of
+                /// range for the representation.
+                #[inline]
+                pub const fn from_index(index: u32) -> Self {
+                    if index < Self::REPR_BITS {
+                        Self(1 << index)
+                    } else {
+                        Self(0)
+                    }
+                }
+
+                /// Keeps undefined bits as-is.
+                #[inline]
+                pub const fn from_bits_retain(bits: $repr) -> Self {
+                    Self(bits)
+                }
+
+                #[inline]
+                pub fn to_le_bytes(self) -> [u8; ::core::mem::size_of::<$repr>()] {
+                    self.0.to_le_bytes()
+                }
+
+                #[inline]
+                pub fn to_be_bytes(self) -> [u8; ::core::mem::size_of::<$repr>()] {
+                    self.0.to_be_bytes()
+                }
+
+                #[inline]
+                pub fn to_ne_bytes(self) -> [u8; ::core::mem::size_of::<$repr>()] {
+                    self.0.to_ne_bytes()
+                }
+
+                /// Returns `None` if `bytes` contains undefined bits.
+                #[inline]
+                pub fn from_le_bytes(bytes: [u8; ::core::mem::size_of::<$repr>()]) -> Option<Self> {
+                    Self::from_bits(<$repr>::from_le_bytes(bytes))
+                }
+
+                /// Returns `None` if `bytes` contains undefined bits.
+                #[inline]
+                pub fn from_be_bytes(bytes: [u8; ::core::mem::size_of::<$repr>()]) -> Option<Self> {
+                    Self::from_bits(<$repr>::from_be_bytes(bytes))
+                }
+
+                /// Returns `None` if `bytes` contains undefined bits.
+                #[inline]
+                pub fn from_ne_bytes(bytes: [u8; ::core::mem::size_of::<$repr>()]) -> Option<Self> {
+                    Self::from_bits(<$repr>::from_ne_bytes(bytes))
+                }
+
+                /// Discards undefined bits.
+                #[inline]
+                pub fn from_le_bytes_truncate(bytes: [u8; ::core::mem::size_of::<$repr>()]) -> Self {
+                    Self::from_bits_truncate(<$repr>::from_le_bytes(bytes))
+                }
+
+                /// Discards undefined bits.
+                #[inline]
+                pub fn from_be_bytes_truncate(bytes: [u8; ::core::mem::size_of::<$repr>()]) -> Self {
+                    Self::from_bits_truncate(<$repr>::from_be_bytes(bytes))
+                }
+
+                /// Discards undefined bits.
+                #[inline]
+                pub fn from_ne_bytes_truncate(bytes: [u8; ::core::mem::size_of::<$repr>()]) -> Self {
+                    Self::from_bits_truncate(<$repr>::from_ne_bytes(bytes))
+                }
+
+                /// Returns true if exactly one defined flag is set.
+                #[inline]
+                pub fn is_single(&self) -> bool {
+                    (self.0 & Self::MASK).count_ones() == 1
+                }
+
+                /// The only defined flag that is set, ignoring undefined bits.
+                #[inline]
+                pub fn try_single(&self) -> Result<Self, $crate::SingleFlagError> {
+                    match self.0 & Self::MASK {
+                        0 => Err($crate::SingleFlagError::Empty),
+                        bits if bits.count_ones() == 1 => Ok(Self(bits)),
+                        _ => Err($crate::SingleFlagError::Multiple),
+                    }
+                }
+
+                #[inline]
+                pub fn contains(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
+                    other.in_set(self)
+                }
+
+                /// Returns true if no bits outside `mask` are set, including undefined bits.
+                #[inline]
+                pub fn contains_only(&self, mask: impl $crate::SetMember<Set=Self>) -> bool {
+                    self.0 & !mask.to_set().0 == 0
+                }
+
+                #[inline]
+                pub fn equals(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
+                    other.eq_set(self)
+                }
+
+                /// `const` version of `intersects` for concrete values.
+                #[inline]
+                pub const fn intersects_const(&self, other: Self) -> bool {
+                    self.0 & other.0 != 0
+                }
+
+                /// Returns true if the same defined flags are set, ignoring undefined bits.
+                #[inline]
+                pub fn matches_exactly(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
+                    self.normalized() == other.to_set().normalized()
+                }
+
+                /// Applies `op` to `self` and `other`.
+                #[inline]
+                pub fn combine(&self, op: $crate::SetOp, other: impl $crate::SetMember<Set=Self>) -> Self {
+                    let other = other.to_set().0;
+                    Self(match op {
+                        $crate::SetOp::Union => self.0 | other,
+                        $crate::SetOp::Intersection => self.0 & other,
+                        $crate::SetOp::Difference => self.0 & !other,
+                        $crate::SetOp::SymmetricDifference => self.0 ^ other,
+                    })
+                }
+
+                /// Only the bits of `self` that are also in `mask`, the same as `self & mask`.
+                #[inline]
+                pub fn truncate_to(&self, mask: impl $crate::SetMember<Set=Self>) -> Self {
+                    Self(self.0 & mask.to_set().0)
+                }
+
+                /// Clears all undefined bits.
+                #[inline]
+                pub const fn normalized(&self) -> Self {
+                    Self(self.0 & Self::MASK)
+                }
+
+                /// `const` version of `contains` for concrete values.
+                #[inline]
+                pub const fn contains_const(&self, other: Self) -> bool {
+                    self.0 & other.0 == other.0
+                }
+
+                /// Returns true if every bit of `self` is set in `other`, the reverse of
+                /// [`contains_const`](Self::contains_const).
+                #[inline]
+                pub const fn is_subset_const(&self, other: Self) -> bool {
+                    self.0 & other.0 == self.0
+                }
+
+                /// `const` version of `|` for concrete values.
+                #[inline]
+                pub const fn union_const(&self, other: Self) -> Self {
+                    Self(self.0 | other.0)
+                }
+
+                /// `const` version of `&` for concrete values.
+                #[inline]
+                pub const fn intersection_const(&self, other: Self) -> Self {
+                    Self(self.0 & other.0)
+                }
+
+                /// `const` version of `-` for concrete values.
+                #[inline]
+                pub const fn difference_const(&self, other: Self) -> Self {
+                    Self(self.0 & !other.0)
+                }
+
+                /// Defined flags not set in `self`. Undefined bits are cleared.
+                #[inline]
+                pub const fn complement_const(&self) -> Self {
+                    Self(!self.0 & Self::MASK)
+                }
+
+                #[inline]
+                pub fn intersects(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
+                    self.0 & other.to_set().0 != 0
+                }
+
+                /// Raw bits set in both `self` and `other`.
+                #[inline]
+                pub fn common_bits(&self, other: impl $crate::SetMember<Set=Self>) -> $repr {
+                    self.0 & other.to_set().0
+                }
+
+                /// Returns true if `self` and `raw` agree on every defined flag. Undefined
+                /// bits of both are ignored, unlike `==` which compares them exactly
+                /// unless `ignore_reserved_bits` is set.
+                #[inline]
+                pub const fn matches_mask(&self, raw: $repr) -> bool {
+                    self.0 & Self::MASK == raw & Self::MASK
+                }
+
+                /// Returns true if `self` and `other` share no bits.
+                #[inline]
+                pub fn is_disjoint(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
+                    !self.intersects(other)
+                }
+
+                /// Returns true if `self` and `other` share no bits, the same as
+                /// [`is_disjoint`](Self::is_disjoint).
+                #[inline]
+                pub fn is_exclusive_with(&self, other: impl $crate::SetMember<Set=Self>) -> bool {
+                    self.is_disjoint(other)
+                }
+
+                /// Sets `member`, clearing the other flags of every exclusive group
+                /// it belongs to.
+                ///
+                /// `member` must have at most one flag of each group. Debug builds
+                /// panic otherwise; release builds set all of them.
+                #[inline]
+                pub fn set_exclusive(&mut self, member: impl $crate::SetMember<Set=Self>) {
+                    let bits = member.to_set().0;
+                    $(debug_assert!(
+                        (bits & Self::$group.0).count_ones() <= 1,
+                        "member has several flags of one exclusive group",
+                    );)*
+                    $(if bits & Self::$group.0 != 0 {
+                        self.0 &= !Self::$group.0;
+                    })*
+                    self.0 |= bits;
+                }
+
+                /// Sets `member` unless that would leave more than one flag of an
+                /// exclusive group set, in which case `self` is left unchanged. The
+                /// error holds the conflicting flags already set, or the flags of
+                /// `member` if it has several flags of one group itself.
+                #[inline]
+                pub fn try_insert(&mut self, member: impl $crate::SetMember<Set=Self>) -> Result<(), $crate::ExclusivityError<Self>> {
+                    let bits = member.to_set().0;
+                    let conflict: $repr = 0 $(| {
+                        let group = Self::$group.0;
+                        if bits & group == 0 || ((self.0 | bits) & group).count_ones() <= 1 {
+                            0
+                        } else if self.0 & group & !bits != 0 {
+                            self.0 & group & !bits
+                        } else {
+                            bits & group
+                        }
+                    })*;
+                    if conflict != 0 {
+                        return Err($crate::ExclusivityError { conflict: Self(conflict) });
+                    }
+                    self.0 |= bits;
+                    Ok(())
+                }
+
+                /// Number of flags of `mask` that are set.
+                #[inline]
+                pub fn count_within(&self, mask: impl $crate::SetMember<Set=Self>) -> u32 {
+                    (self.0 & mask.to_set().0).count_ones()
+                }
+
+                /// Number of defined flags set in exactly one of `self` and `other`.
+                #[inline]
+                pub fn difference_count(&self, other: &Self) -> u32 {
+                    ((self.0 ^ other.0) & Self::MASK).count_ones()
+                }
+
+                /// Returns true if exactly one flag of `group` is set.
+                #[inline]
+                pub fn exactly_one_of(&self, group: impl $crate::SetMember<Set=Self>) -> bool {
+                    (self.0 & group.to_set().0).count_ones() == 1
+                }
+
+                /// Returns false if any exclusive group has more than one member set.
+                #[inline]
+                pub fn is_valid(&self) -> bool {
+                    true $(&& (self.0 & Self::$group.0).count_ones() <= 1)*
+                }
+
+                /// Adds `member` if `cond` is true.
+                #[inline]
+                #[must_use]
+                pub fn merge_if(self, cond: bool, member: impl $crate::SetMember<Set=Self>) -> Self {
+                    if cond {
+                        Self(self.0 | member.to_set().0)
+                    } else {
+                        self
+                    }
+                }
+
+                /// Flips every defined flag, leaving undefined bits untouched.
+                #[inline]
+                pub fn toggle_all(&mut self) {
+                    self.0 ^= Self::MASK;
+                }
+
+                /// Flips the flags in `mask`, leaving all other bits untouched.
+                #[inline]
+                pub fn invert_within(&self, mask: impl $crate::SetMember<Set=Self>) -> Self {
+                    Self(self.0 ^ (mask.to_set().0 & Self::MASK))
+                }
+
+                /// Keeps the set flags for which `f` returns true and clears the others,
+                /// leaving undefined bits untouched.
+                #[inline]
+                pub fn retain(&mut self, f: impl FnMut(Self) -> bool) {
+                    self.retain_within(Self::ALL, f)
+                }
+
+                /// Like [`retain`](Self::retain), but only flags in `mask` are passed to
+                /// `f`. Flags outside `mask` are kept regardless of `f`.
+                #[inline]
+                pub fn retain_within(&mut self, mask: impl $crate::SetMember<Set=Self>, mut f: impl FnMut(Self) -> bool) {
+                    let mask = mask.to_set().0;
+                    for flag in Self::ALL_FLAGS {
+                        if self.0 & mask & flag.0 == flag.0 && !f(flag) {
+                            self.0 &= !flag.0;
+                        }
+                    }
+                }
+
+                /// Smallest combination of defined flags for which `f` returns true, or
+                /// `None` if there is none. Among combinations of equal size, the one
+                /// using the earliest declared flags wins.
+                ///
+                /// This tries every one of the `2^COUNT` combinations and panics if
+                /// there are more than 16 flags.
+                #[inline]
+                pub fn minimal_cover(mut f: impl FnMut(Self) -> bool) -> Option<Self> {
+                    assert!(Self::COUNT <= 16, "minimal_cover supports at most 16 flags");
+                    let mut best = None;
+                    let mut best_size = u32::MAX;
+                    for combination in 0u32..1 << Self::COUNT {
+                        let size = combination.count_ones();
+                        if size >= best_size {
+                            continue;
+                        }
+                        let mut flags = Self(0);
+                        for (index, flag) in Self::ALL_FLAGS.into_iter().enumerate() {
+                            if combination & (1 << index) != 0 {
+                                flags.0 |= flag.0;
+                            }
+                        }
+                        if f(flags) {
+                            best = Some(flags);
+                            best_size = size;
+                        }
+                    }
+                    best
+                }
+
+                /// Clears the flags in `mask`, leaving all other bits untouched.
+                #[inline]
+                pub fn clear_within(&mut self, mask: impl $crate::SetMember<Set=Self>) {
+                    self.0 &= !mask.to_set().0;
+                }
+
+                /// Replaces the bits in `mask` with those of `value`, leaving all other
+                /// bits untouched. Bits of `value` outside `mask` are ignored.
+                #[inline]
+                pub fn clear_and_set(&mut self, mask: impl $crate::SetMember<Set=Self>, value: impl $crate::SetMember<Set=Self>) {
+                    let mask = mask.to_set().0;
+                    self.0 = (self.0 & !mask) | (value.to_set().0 & mask);
+                }
+
+                #[inline]
+                pub const fn all() -> Self {
+                    let mut bits = 0;
+                    $($(#[$iter_cfg])* { bits |= Self::$name.0; })*
+                    Self(bits)
+                }
+
+                /// Whether each defined flag is set, in declaration order.
+                #[inline]
+                pub const fn into_bools(self) -> [bool; Self::COUNT] {
+                    let mut bools = [false; Self::COUNT];
+                    let mut index = 0;
+                    while index < Self::COUNT {
+                        bools[index] = self.contains_const(Self::ALL_FLAGS[index]);
+                        index += 1;
+                    }
+                    bools
+                }
+
+                /// Union of every set in `iter`, empty if `iter` is empty.
+                #[inline]
+                pub fn union_all<I: IntoIterator<Item = Self>>(iter: I) -> Self {
+                    iter.into_iter().fold(Self(0), |acc, flags| Self(acc.0 | flags.0))
+                }
+
+                /// Flags common to every set in `iter`.
+                ///
+                /// Returns [`all()`](Self::all) if `iter` is empty.
+                #[inline]
+                pub fn intersection_all<I: IntoIterator<Item = Self>>(iter: I) -> Self {
+                    iter.into_iter().fold(Self::all(), |acc, flags| Self(acc.0 & flags.0))
+                }
+
+                /// Every defined flag, in declaration order.
+                #[inline]
+                pub const fn all_flags() -> &'static [Self] {
+                    &Self::ALL_FLAGS
+                }
+
+                /// Names of every defined flag, in declaration order.
+                #[inline]
+                pub const fn names() -> &'static [&'static str] {
+                    &[$($(#[$iter_cfg])* $crate::tlbf!(@name $name $($rename)?)),*]
+                }
+
+                /// `(name, bit index, bits)` of every defined flag, in declaration order.
+                #[inline]
+                pub const fn metadata() -> &'static [(&'static str, u32, $repr)] {
+                    &[$($(#[$iter_cfg])* ($crate::tlbf!(@name $name $($rename)?), ($value) as u32, Self::$name.0)),*]
+                }
+
+                /// Names of the set flags in declaration order.
+                #[inline]
+                pub fn names_iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+                    Self::metadata()
+                        .iter()
+                        .filter(move |(.., bits)| self.0 & bits == *bits)
+                        .map(|(name, ..)| *name)
+                }
+
+                /// Names of the set flags in lexicographic order.
+                #[inline]
+                pub fn iter_names_sorted(&self) -> impl Iterator<Item = &'static str> {
+                    let mut names = [""; Self::COUNT];
+                    let mut len = 0;
+                    for (name, _, bits) in Self::metadata() {
+                        if self.0 & bits == *bits {
+                            names[len] = name;
+                            len += 1;
+                        }
+                    }
+                    names[..len].sort_unstable();
+                    names.into_iter().take(len)
+                }
+
+                /// Name of the flag if exactly one defined flag is set.
+                #[inline]
+                pub fn name(&self) -> Option<&'static str> {
+                    if !self.is_single() {
+                        return None;
+                    }
+                    let bits = self.0 & Self::MASK;
+                    Self::all_flags()
+                        .iter()
+                        .position(|flag| flag.0 == bits)
+                        .map(|index| Self::names()[index])
+                }
+
+                /// Splits off the lowest defined flag, returning it and the rest of
+                /// the set, or `None` if no defined flag is set.
+                #[inline]
+                pub fn split_first(&self) -> Option<(Self, Self)> {
+                    let bits = self.0 & Self::MASK;
+                    if bits == 0 {
+                        return None;
+                    }
+                    let lowest = bits & bits.wrapping_neg();
+                    Some((Self(lowest), Self(self.0 & !lowest)))
+                }
+
+                /// The highest set defined flag.
+                #[inline]
+                pub fn highest(&self) -> Option<Self> {
+                    let bits = self.0 & Self::MASK;
+                    if bits == 0 {
+                        return None;
+                    }
+                    Some(Self(1 << (Self::REPR_BITS - 1 - bits.leading_zeros())))
+                }
+
+                /// Bit index of the highest set defined flag.
+                #[inline]
+                pub fn highest_index(&self) -> Option<u32> {
+                    let bits = self.0 & Self::MASK;
+                    if bits == 0 {
+                        return None;
+                    }
+                    Some(Self::REPR_BITS - 1 - bits.leading_zeros())
+                }
+
+                /// The lowest set defined flag.
+                #[inline]
+                pub fn lowest(&self) -> Option<Self> {
+                    let bits = self.0 & Self::MASK;
+                    if bits == 0 {
+                        return None;
+                    }
+                    Some(Self(bits & bits.wrapping_neg()))
+                }
+
+                /// Bit index of the lowest set defined flag.
+                #[inline]
+                pub fn lowest_index(&self) -> Option<u32> {
+                    let bits = self.0 & Self::MASK;
+                    if bits == 0 {
+                        return None;
+                    }
+                    Some(bits.trailing_zeros())
+                }
+
+                /// Removes and returns the lowest defined flag.
+                #[inline]
+                pub fn pop(&mut self) -> Option<Self> {
+                    let (first, rest) = self.split_first()?;
+                    *self = rest;
+                    Some(first)
+                }
+
+                /// Looks up a single flag by name, compiled to a `match` on the name.
+                #[inline]
+                pub fn from_name(name: &str) -> Option<Self> {
+                    match name {
+                        $($(#[$cfg])* $crate::tlbf!(@name $name $($rename)?) => Some(Self::$name),)*
+                        _ => None,
+                    }
+                }
+
+                #[doc(hidden)]
+                #[inline]
+                fn from_name_bits(name: &str) -> Option<$repr> {
+                    Self::from_name(name).map(|flags| flags.0)
+                }
+
+                /// Parses either a number or flag names in the `FromStr` format.
+                ///
+                /// Numbers may be prefixed by `0b`, `0o` or `0x`. Neither form may
+                /// contain undefined bits, including hex parts of the `FromStr` format.
+                #[inline]
+                pub fn parse(s: &str) -> Result<Self, $crate::ParseError> {
+                    let s = s.trim();
+                    let bits = match $crate::__private::parse_number(s) {
+                        Some(bits) => bits?,
+                        None => s.parse::<Self>()?.0,
+                    };
+                    Self::from_bits(bits).ok_or($crate::ParseError::UndefinedBits)
+                }
+
+                /// The flag declared after this one, wrapping from the last flag
+                /// back to the first.
+                ///
+                /// Returns `None` unless exactly one defined flag is set.
+                #[inline]
+                pub fn next_single(&self) -> Option<Self> {
+                    if !self.is_single() {
+                        return None;
+                    }
+                    let bits = self.0 & Self::MASK;
+                    let index = Self::all_flags().iter().position(|flag| flag.0 == bits)?;
+                    Self::flag_at(index + 1).or_else(|| Self::flag_at(0))
+                }
+
+                /// Bit indices of the set flags in ascending order, skipping undefined bits.
+                #[inline]
+                pub fn iter_indices(&self) -> impl Iterator<Item = u32> {
+                    let mut bits = self.0 & Self::MASK;
+                    ::core::iter::from_fn(move || {
+                        if bits == 0 {
+                            return None;
+                        }
+                        let index = bits.trailing_zeros();
+                        bits &= !(1 << index);
+                        Some(index)
+                    })
+                }
+
+                /// Rotates which flags are set by `by` positions among the defined flags
+                /// ordered by bit index, ignoring gaps between them. Positive values move
+                /// toward higher bits, and flags past the highest wrap around to the
+                /// lowest. Undefined bits are left untouched.
+                #[inline]
+                pub fn rotate(&self, by: i32) -> Self {
+                    if Self::COUNT == 0 {
+                        return *self;
+                    }
+                    let mut positions = [0; Self::COUNT];
+                    for (position, index) in positions.iter_mut().zip(Self::ALL.iter_indices()) {
+                        *position = index;
+                    }
+                    let shift = (by as i64).rem_euclid(Self::COUNT as i64) as usize;
+                    let mut bits = self.0 & !Self::MASK;
+                    for (index, target) in positions.iter().zip(positions.iter().cycle().skip(shift)) {
+                        if self.0 & (1 << index) != 0 {
+                            bits |= 1 << target;
+                        }
+                    }
+                    Self(bits)
+                }
+
+                /// Mirrors which flags are set: the flag at position `i` among the
+                /// defined flags ordered by bit index moves to position `COUNT - 1 - i`.
+                /// Positions ignore gaps between flags, so no undefined bits are
+                /// produced. Undefined bits are left untouched.
+                #[inline]
+                pub fn reverse_flags(&self) -> Self {
+                    let mut positions = [0; Self::COUNT];
+                    for (position, index) in positions.iter_mut().zip(Self::ALL.iter_indices()) {
+                        *position = index;
+                    }
+                    let mut bits = self.0 & !Self::MASK;
+                    for (index, target) in positions.iter().zip(positions.iter().rev()) {
+                        if self.0 & (1 << index) != 0 {
+                            bits |= 1 << target;
+                        }
+                    }
+                    Self(bits)
+                }
+
+                /// Sets or clears the flag at raw bit position `index`.
+                ///
+                /// # Panics
+                ///
+                /// If no defined flag occupies bit `index`.
+                #[inline]
+                pub const fn with_bit(self, index: u32, value: bool) -> Self {
+                    let bit = Self::from_index(index).0;
+                    assert!(bit & Self::MASK != 0, "no flag defined at this bit index");
+                    if value {
+                        Self(self.0 | bit)
+                    } else {
+                        Self(self.0 & !bit)
+                    }
+                }
+
+                /// The `index`-th defined flag in declaration order.
+                #[inline]
+                pub fn flag_at(index: usize) -> Option<Self> {
+                    Self::all_flags().get(index).copied()
+                }
+            }
+
+            impl $crate::Flags for $flags_name {
+                const EMPTY: Self = Self(0);
+            }
+
+            impl $crate::Bits for $flags_name {
+                #[inline]
+                fn raw(&self) -> u128 {
+                    (self.0 as u128) & (u128::MAX >> (128 - Self::REPR_BITS))
+                }
+            }
+
+            impl $crate::SetMember for $flags_name {
+                type Set = $flags_name;
+                #[inline]
+                fn to_set(&self) -> Self::Set {
+                    *self
+                }
+                #[inline]
+                fn as_set(&self) -> Option<&Self::Set> {
+                    Some(self)
+                }
+                #[inline]
+                fn eq_set(&self, set: &Self::Set) -> bool {
+                    self == set
+                }
+                #[inline]
+                fn in_set(&self, set: &Self::Set) -> bool {
+                    set.0 & self.0 == self.0
+                }
+            }
+
+            impl ::core::convert::TryFrom<&[&str]> for $flags_name {
+                type Error = $crate::ParseError;
+
+                #[inline]
+                fn try_from(names: &[&str]) -> Result<Self, Self::Error> {
+                    $crate::__private::from_names(Self::from_name_bits, names.iter().copied()).map(Self)
+                }
+            }
+
+            impl ::core::fmt::Binary for $flags_name {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Binary::fmt(&self.0, f)
+                }
+            }
+
+            impl ::core::fmt::Octal for $flags_name {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Octal::fmt(&self.0, f)
+                }
+            }
+
+            impl ::core::fmt::LowerHex for $flags_name {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::LowerHex::fmt(&self.0, f)
+                }
+            }
+
+            impl ::core::fmt::UpperHex for $flags_name {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::UpperHex::fmt(&self.0, f)
+                }
+            }
+
+            /// Sets each defined flag whose entry is true, in declaration order.
+            impl ::core::convert::From<[bool; $flags_name::COUNT]> for $flags_name {
+                #[inline]
+                fn from(bools: [bool; $flags_name::COUNT]) -> Self {
+                    let mut result = Self(0);
+                    for (flag, set) in Self::ALL_FLAGS.iter().zip(bools) {
+                        if set {
+                            result.0 |= flag.0;
+                        }
+                    }
+                    result
+                }
+            }
+
+            /// Compares the same bits as `Eq`.
+            impl ::core::cmp::PartialEq<$repr> for $flags_name {
+                #[inline]
+                fn eq(&self, other: &$repr) -> bool {
+                    self.__eq_bits() == Self(*other).__eq_bits()
+                }
+            }
+
+            impl ::core::cmp::PartialEq<$flags_name> for $repr {
+                #[inline]
+                fn eq(&self, other: &$flags_name) -> bool {
+                    other == self
+                }
+            }
+
+            impl<T> ::core::ops::BitAnd<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                type Output = Self;
+                #[inline]
+                fn bitand(self, rhs: T) -> Self {
+                    Self(self.0 & rhs.to_set().0)
+                }
+            }
+
+            impl<T> ::core::ops::BitOr<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                type Output = Self;
+                #[inline]
+                fn bitor(self, rhs: T) -> Self {
+                    Self(self.0 | rhs.to_set().0)
+                }
+            }
+
+            impl<T> ::core::ops::BitXor<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                type Output = Self;
+                #[inline]
+                fn bitxor(self, rhs: T) -> Self {
+                    Self(self.0 ^ rhs.to_set().0)
+                }
+            }
+
+            impl<T> ::core::ops::Sub<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                type Output = Self;
+                #[inline]
+                fn sub(self, rhs: T) -> Self {
+                    Self(self.0 & !rhs.to_set().0)
+                }
+            }
+
+            impl<T> ::core::ops::BitAndAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                #[inline]
+                fn bitand_assign(&mut self, rhs: T) {
+                    self.0 &= rhs.to_set().0
+                }
+            }
+
+            /// Debug builds panic if `rhs` carries bits of neither a flag nor an
+            /// alias, unless `ignore_reserved_bits` is set. Release builds don't check.
+            impl<T> ::core::ops::BitOrAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                #[inline]
+                fn bitor_assign(&mut self, rhs: T) {
+                    let rhs = rhs.to_set().0;
+                    debug_assert!(
+                        Self::__IGNORES_RESERVED_BITS || rhs & Self::ASSIGNABLE_BITS == rhs,
+                        "rhs carries undefined bits",
+                    );
+                    self.0 |= rhs
+                }
+            }
+
+            /// Debug builds panic if `rhs` carries bits of neither a flag nor an
+            /// alias, unless `ignore_reserved_bits` is set. Release builds don't check.
+            impl<T> ::core::ops::BitXorAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                #[inline]
+                fn bitxor_assign(&mut self, rhs: T) {
+                    let rhs = rhs.to_set().0;
+                    debug_assert!(
+                        Self::__IGNORES_RESERVED_BITS || rhs & Self::ASSIGNABLE_BITS == rhs,
+                        "rhs carries undefined bits",
+                    );
+                    self.0 ^= rhs
+                }
+            }
+
+            impl<T> ::core::ops::SubAssign<T> for $flags_name where T: $crate::SetMember<Set = Self> {
+                #[inline]
+                fn sub_assign(&mut self, rhs: T) {
+                    self.0 &= !rhs.to_set().0
+                }
+            }
+        };
+
+        $crate::tlbf! (@option [$flags_name: $repr] $($opts)*);
+        $crate::tlbf! (@debug [$flags_name: $repr] [$($opts)*]);
+        $crate::tlbf! (@display [$flags_name: $repr] [$($opts)*]);
+        $crate::tlbf! (@kind [$vis $flags_name: $repr] [$($(#[$cfg])* $name)*] [$($opts)*]);
+        $crate::__tlbf_alloc!($flags_name: $repr);
+        $crate::__tlbf_borsh!($flags_name: $repr);
+        $crate::__tlbf_serde!($flags_name: $repr);
+
+        $(
+            $(#[$cfg])*
+            #[allow(deprecated)]
+            const _: $flags_name = $flags_name::$name;
+        )*
+
+        #[allow(deprecated)]
+        const _: () = {
+            // Includes flags hidden from `metadata` by `skip_deprecated`.
+            let flags: &[$crate::__private::Meta<$repr>] = &[
+                $($(#[$cfg])* ($crate::tlbf!(@name $name $($rename)?), ($value) as u32, $flags_name::$name.0)),*
+            ];
+            $crate::__private::assert_disjoint(flags)
+        };
+
+        const _: () = {
+            let metadata = $flags_name::metadata();
+            let mut bits = 0;
+            let mut index = 0;
+            while index < metadata.len() {
+                bits |= metadata[index].2;
+                index += 1;
+            }
+            assert!(bits == $flags_name::all().0, "`all()` does not match the defined flags");
+            assert!(
+                bits.count_ones() as usize == $flags_name::COUNT && metadata.len() == $flags_name::COUNT,
+                "`COUNT` does not match the defined flags",
+            );
+        };
+
+
+        $(
+            $(#[$cfg])*
+            $(#[$($branch_args)*])*
+            #[derive(Debug, Default, Clone, Copy, Eq, Hash)]
+            $vis2 struct $name;
+
+            $(#[$cfg])*
+            #[allow(deprecated)]
+            const _: () = {
+                use $crate::SetMember;
+                impl $name {
+                    /// Raw bit position of this flag.
+                    #[inline]
+                    pub const fn bit_index(&self) -> u32 {
+                        ($value) as u32
+                    }
+                }
 
-            const _: () = {
-                use $crate::SetMember;
                 impl ::core::fmt::Display for $name {
+                    #[inline]
                     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                        f.write_str(stringify!($name))
+                        f.write_str($crate::tlbf!(@name $name $($rename)?))
+                    }
+                }
+    
+                impl ::core::convert::From<$name> for $flags_name {
+                    #[inline]
+                    fn from(_: $name) -> Self {
+                        Self::$name
+                    }
+                }
+
+                impl ::core::convert::From<&$name> for $flags_name {
+                    #[inline]
+                    fn from(_: &$name) -> Self {
+                        Self::$name
+                    }
+                }
+    
+                impl $crate::SetMember for $name {
+                    type Set = $flags_name;
+                    #[inline]
+                    fn to_set(&self) -> Self::Set {
+                        self.into()
+                    }
+                    #[inline]
+                    fn eq_set(&self, set: &Self::Set) -> bool {
+                        set == &Self::Set::$name
+                    }
+                    #[inline]
+                    fn in_set(&self, set: &Self::Set) -> bool {
+                        *set & Self::Set::$name == Self::Set::$name
+                    }
+                }
+    
+                impl<T> ::core::ops::BitOr<T> for $name where T: SetMember<Set=$flags_name>{
+                    type Output = $flags_name;
+                    #[inline]
+                    fn bitor(self, rhs: T) -> $flags_name {
+                        $flags_name::$name | rhs.to_set()
+                    }
+                }
+
+                impl<T> ::core::ops::Sub<T> for $name where T: SetMember<Set=$flags_name>{
+                    type Output = $flags_name;
+                    #[inline]
+                    fn sub(self, rhs: T) -> $flags_name {
+                        $flags_name::from_bits_retain(self.to_set().0 & !rhs.to_set().0)
+                    }
+                }
+
+                impl ::core::ops::Not for $name {
+                    type Output = $flags_name;
+                    #[inline]
+                    fn not(self) -> $flags_name {
+                        $flags_name::from_bits_retain($flags_name::MASK & !$flags_name::$name.0)
+                    }
+                }
+
+                impl<T> ::core::cmp::PartialEq<T> for $name where T: $crate::SetMember<Set=$flags_name>{
+                    #[inline]
+                    fn eq(&self, other: &T) -> bool {
+                        $flags_name::$name == other.to_set()
+                    }
+                }
+            };
+        )*
+    };
+}
+
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::{FlagRepr, ParseError};
+
+    #[cfg(feature = "alloc")]
+    pub extern crate alloc;
+    #[cfg(feature = "borsh")]
+    pub use borsh;
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    /// Metadata of a flag: name, bit index and bits.
+    pub type Meta<R> = (&'static str, u32, R);
+
+    pub fn from_names<'a, R: FlagRepr>(
+        from_name: impl Fn(&str) -> Option<R>,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<R, ParseError> {
+        names.into_iter().try_fold(R::ZERO, |bits, name| {
+            Ok(bits | from_name(name).ok_or(ParseError::UnknownName)?)
+        })
+    }
+
+    pub fn fmt<R: FlagRepr>(
+        bits: R,
+        mask: R,
+        metadata: &[Meta<R>],
+        sep: &str,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        let mut first = true;
+        for (name, _, flag) in metadata {
+            if bits & *flag == *flag {
+                if !first {
+                    f.write_str(sep)?;
+                }
+                first = false;
+                f.write_str(name)?;
+            }
+        }
+        let undefined = bits & !mask;
+        if undefined != R::ZERO {
+            if !first {
+                f.write_str(sep)?;
+            }
+            core::write!(f, "{:#x}", undefined)?;
+        }
+        Ok(())
+    }
+
+    pub fn from_str<R: FlagRepr>(from_name: impl Fn(&str) -> Option<R>, sep: &str, s: &str) -> Result<R, ParseError> {
+        if s.trim().is_empty() {
+            return Ok(R::ZERO);
+        }
+        let sep = if sep.trim().is_empty() { sep } else { sep.trim() };
+        s.split(sep).try_fold(R::ZERO, |bits, name| {
+            let name = name.trim();
+            Ok(bits | match name.strip_prefix("0x") {
+                Some(hex) => R::from_str_radix(hex, 16).map_err(|_| ParseError::InvalidNumber)?,
+                None => from_name(name).ok_or(ParseError::UnknownName)?,
+            })
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn to_json_array<R: FlagRepr>(bits: R, metadata: &[Meta<R>]) -> alloc::string::String {
+        let mut json = alloc::string::String::from("[");
+        for (name, _, flag) in metadata {
+            if bits & *flag == *flag {
+                if json.len() > 1 {
+                    json.push(',');
+                }
+                json.push('"');
+                for c in name.chars() {
+                    if matches!(c, '"' | '\\') {
+                        json.push('\\');
+                    }
+                    json.push(c);
+                }
+                json.push('"');
+            }
+        }
+        json.push(']');
+        json
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn from_json_array<R: FlagRepr>(from_name: impl Fn(&str) -> Option<R>, s: &str) -> Result<R, ParseError> {
+        let inner = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or(ParseError::InvalidSyntax)?;
+        let mut bits = R::ZERO;
+        let mut chars = inner.trim_start().chars();
+        if chars.as_str().is_empty() {
+            return Ok(bits);
+        }
+        loop {
+            if chars.next() != Some('"') {
+                return Err(ParseError::InvalidSyntax);
+            }
+            let mut name = alloc::string::String::new();
+            loop {
+                match chars.next().ok_or(ParseError::InvalidSyntax)? {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some(c @ ('"' | '\\' | '/')) => name.push(c),
+                        _ => return Err(ParseError::InvalidSyntax),
+                    },
+                    c => name.push(c),
+                }
+            }
+            bits = bits | from_name(&name).ok_or(ParseError::UnknownName)?;
+            let rest = chars.as_str().trim_start();
+            match rest.strip_prefix(',') {
+                Some(rest) => chars = rest.trim_start().chars(),
+                None if rest.is_empty() => return Ok(bits),
+                None => return Err(ParseError::InvalidSyntax),
+            }
+        }
+    }
+
+    /// Fails const evaluation with the names of the first two flags sharing a bit.
+    pub const fn assert_disjoint<R>(metadata: &[Meta<R>]) {
+        let mut i = 0;
+        while i < metadata.len() {
+            let mut j = i + 1;
+            while j < metadata.len() {
+                if metadata[i].1 == metadata[j].1 {
+                    let msg = Message::new()
+                        .push("flags `")
+                        .push(metadata[i].0)
+                        .push("` and `")
+                        .push(metadata[j].0)
+                        .push("` share a bit");
+                    panic!("{}", msg.as_str());
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+    }
+
+    /// Fails const evaluation unless every flag of `narrow` is in `wide` with the same bit.
+    pub const fn assert_widens<R1, R2>(narrow: &[Meta<R1>], narrow_bits: u32, wide: &[Meta<R2>], wide_bits: u32) {
+        assert!(narrow_bits <= wide_bits, "cannot widen into a narrower representation");
+        let mut i = 0;
+        while i < narrow.len() {
+            let mut j = 0;
+            while j < wide.len() && !str_eq(narrow[i].0, wide[j].0) {
+                j += 1;
+            }
+            if j == wide.len() || narrow[i].1 != wide[j].1 {
+                let msg = Message::new()
+                    .push("flag `")
+                    .push(narrow[i].0)
+                    .push("` is not at the same bit in the wider type");
+                panic!("{}", msg.as_str());
+            }
+            i += 1;
+        }
+    }
+
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Fixed capacity string buffer usable in const panics.
+    ///
+    /// Built by value rather than through `&mut self`, which const fns only
+    /// accept since Rust 1.83.
+    struct Message {
+        buf: [u8; 256],
+        len: usize,
+    }
+
+    impl Message {
+        const fn new() -> Self {
+            Message { buf: [0; 256], len: 0 }
+        }
+
+        const fn push(mut self, s: &str) -> Self {
+            let bytes = s.as_bytes();
+            // Pieces that do not fit are dropped whole, keeping the buffer valid UTF-8.
+            if self.len + bytes.len() > self.buf.len() {
+                return self;
+            }
+            let mut i = 0;
+            while i < bytes.len() {
+                self.buf[self.len] = bytes[i];
+                self.len += 1;
+                i += 1;
+            }
+            self
+        }
+
+        const fn as_str(&self) -> &str {
+            // `split_at` is only const since Rust 1.71.
+            let mut bytes: &[u8] = &self.buf;
+            while bytes.len() > self.len {
+                if let [rest @ .., _] = bytes {
+                    bytes = rest;
+                }
+            }
+            match core::str::from_utf8(bytes) {
+                Ok(s) => s,
+                Err(_) => "flags share a bit",
+            }
+        }
+    }
+
+    /// Parses a number with an optional radix prefix, or `None` if `s` is not a number.
+    pub fn parse_number<R: FlagRepr>(s: &str) -> Option<Result<R, ParseError>> {
+        if !s.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let (digits, radix) = match s.get(..2) {
+            Some("0b" | "0B") => (&s[2..], 2),
+            Some("0o" | "0O") => (&s[2..], 8),
+            Some("0x" | "0X") => (&s[2..], 16),
+            _ => (s, 10),
+        };
+        Some(R::from_str_radix(digits, radix).map_err(|_| ParseError::InvalidNumber))
+    }
+}
+
+/// Implements conversions that require allocation.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tlbf_alloc {
+    ($flags_name: ident: $repr: ty) => {
+        impl ::core::convert::TryFrom<&[$crate::__private::alloc::string::String]> for $flags_name {
+            type Error = $crate::ParseError;
+
+            #[inline]
+            fn try_from(names: &[$crate::__private::alloc::string::String]) -> Result<Self, Self::Error> {
+                $crate::__private::from_names(Self::from_name_bits, names.iter().map(|name| name.as_str()))
+                    .map(Self)
+            }
+        }
+
+        impl $flags_name {
+            /// Names of the set flags as a JSON array, e.g. `["Red","Blue"]`.
+            ///
+            /// Undefined bits are not included.
+            #[inline]
+            pub fn to_json_array(self) -> $crate::__private::alloc::string::String {
+                $crate::__private::to_json_array(self.0, Self::metadata())
+            }
+
+            /// Parse a JSON array of flag names produced by `to_json_array`.
+            #[inline]
+            pub fn from_json_array(s: &str) -> Result<Self, $crate::ParseError> {
+                $crate::__private::from_json_array(Self::from_name_bits, s).map(Self)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tlbf_alloc {
+    ($($tt: tt)*) => {};
+}
+
+/// Implements `borsh` serialization as the underlying representation,
+/// rejecting undefined bits on deserialization.
+#[cfg(feature = "borsh")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tlbf_borsh {
+    ($flags_name: ident: $repr: ty) => {
+        impl $crate::__private::borsh::BorshSerialize for $flags_name {
+            #[inline]
+            fn serialize<W: $crate::__private::borsh::io::Write>(&self, writer: &mut W) -> $crate::__private::borsh::io::Result<()> {
+                $crate::__private::borsh::BorshSerialize::serialize(&self.0, writer)
+            }
+        }
+
+        impl $crate::__private::borsh::BorshDeserialize for $flags_name {
+            #[inline]
+            fn deserialize_reader<R: $crate::__private::borsh::io::Read>(reader: &mut R) -> $crate::__private::borsh::io::Result<Self> {
+                let bits = <$repr as $crate::__private::borsh::BorshDeserialize>::deserialize_reader(reader)?;
+                Self::from_bits(bits).ok_or_else(|| {
+                    $crate::__private::borsh::io::ErrorKind::InvalidData.into()
+                })
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "borsh"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tlbf_borsh {
+    ($($tt: tt)*) => {};
+}
+
+/// Implements `serde` serialization as the `Display` string in human readable
+/// formats and the underlying representation otherwise, rejecting undefined
+/// bits on deserialization.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tlbf_serde {
+    ($flags_name: ident: $repr: ty) => {
+        impl $crate::__private::serde::Serialize for $flags_name {
+            #[inline]
+            fn serialize<S: $crate::__private::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.collect_str(self)
+                } else {
+                    $crate::__private::serde::Serialize::serialize(&self.0, serializer)
+                }
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $flags_name {
+            #[inline]
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                use $crate::__private::serde::de::Error;
+
+                struct Visitor;
+
+                impl $crate::__private::serde::de::Visitor<'_> for Visitor {
+                    type Value = $flags_name;
+
+                    #[inline]
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.write_str(::core::concat!("a string of ", ::core::stringify!($flags_name), " flags"))
+                    }
+
+                    #[inline]
+                    fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+                        let flags: $flags_name = s.parse().map_err(E::custom)?;
+                        $flags_name::from_bits(flags.0).ok_or_else(|| E::custom($crate::ParseError::UndefinedBits))
                     }
                 }
-    
-                impl ::core::convert::From<$name> for $flags_name {
-                    fn from(_: $name) -> Self {
-                        Self::$name
-                    }
+
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(Visitor)
+                } else {
+                    let bits = <$repr as $crate::__private::serde::Deserialize>::deserialize(deserializer)?;
+                    Self::from_bits(bits).ok_or_else(|| D::Error::custom($crate::ParseError::UndefinedBits))
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tlbf_serde {
+    ($($tt: tt)*) => {};
+}
+
+/// The type requiring every one of the given flags, as [`And`] nested to the right.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// fn has_all(set: Color, flags: ty_and!(Red, Green, Blue)) -> bool {
+///     set.contains(flags)
+/// }
+/// assert!(has_all(Color::ALL, Default::default()));
+/// assert!(!has_all(Red | Green, Default::default()));
+/// ```
+#[macro_export]
+macro_rules! ty_and {
+    ($ty: ty $(,)?) => {
+        $ty
+    };
+    ($first: ty, $($rest: ty),+ $(,)?) => {
+        $crate::And<$first, $crate::ty_and!($($rest),+)>
+    };
+}
+
+/// Implements [`SetMember`] for a wrapper around a flags type or a single flag
+/// by delegating to the wrapped field.
+///
+/// This takes the place of a `#[derive(SetMember)]`, which would need a
+/// separate proc-macro crate.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// struct Palette(Color);
+/// struct Primary {
+///     color: Red,
+/// }
+/// impl_set_member!(Palette(Color));
+/// impl_set_member!(Primary { color: Red });
+///
+/// let palette = Palette(Red | Blue);
+/// assert!(Color::ALL.contains(&palette));
+/// assert!(palette.0.contains(Primary { color: Red }));
+/// ```
+///
+/// Other types, including paths and generic types, name the field in braces,
+/// with generic parameters in `impl[..]` and an optional `where` clause.
+/// Tuple fields are named by index.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// mod wrappers {
+///     pub struct Tagged<T>(pub &'static str, pub T);
+/// }
+/// impl_set_member!(impl[T: SetMember] wrappers::Tagged<T> { 1: T });
+///
+/// struct Cached<M> {
+///     member: M,
+///     hits: u32,
+/// }
+/// impl_set_member!(impl[M] Cached<M> { member: M } where M: SetMember<Set = Color>);
+///
+/// let cached = Cached { member: wrappers::Tagged("warm", Red | Green), hits: 0 };
+/// assert_eq!(cached.to_set(), Red | Green);
+/// ```
+#[macro_export]
+macro_rules! impl_set_member {
+    ($ty: ident ($inner: ty)) => {
+        $crate::impl_set_member!(@impl [] $ty, 0, $inner, []);
+    };
+    ($ty: ident {$field: ident: $inner: ty}) => {
+        $crate::impl_set_member!(@impl [] $ty, $field, $inner, []);
+    };
+    (impl [$($generics: tt)*] $ty: ty {$field: tt: $inner: ty} $(where $($bounds: tt)*)?) => {
+        $crate::impl_set_member!(@impl [$($generics)*] $ty, $field, $inner, [$($($bounds)*)?]);
+    };
+    ($ty: ty {$field: tt: $inner: ty} $(where $($bounds: tt)*)?) => {
+        $crate::impl_set_member!(@impl [] $ty, $field, $inner, [$($($bounds)*)?]);
+    };
+    (@impl [$($generics: tt)*] $ty: ty, $field: tt, $inner: ty, [$($bounds: tt)*]) => {
+        impl<$($generics)*> $crate::SetMember for $ty where $($bounds)* {
+            type Set = <$inner as $crate::SetMember>::Set;
+
+            #[inline]
+            fn to_set(&self) -> Self::Set {
+                $crate::SetMember::to_set(&self.$field)
+            }
+
+            #[inline]
+            fn eq_set(&self, set: &Self::Set) -> bool {
+                $crate::SetMember::eq_set(&self.$field, set)
+            }
+
+            #[inline]
+            fn in_set(&self, set: &Self::Set) -> bool {
+                $crate::SetMember::in_set(&self.$field, set)
+            }
+        }
+    };
+}
+
+/// Join bitflags at the type level.
+/// 
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// let flags = tyflags!(Red|Blue);
+/// assert!(flags.contains(Color::Red));
+/// assert!(flags.contains(Color::Blue));
+/// assert!(!flags.contains(Color::Green));
+/// ```
+///
+/// A list of two or more flags is their union as a type. A single flag is
+/// always taken as a value, so use [`flags_type!`] to name any union,
+/// including one with a single member.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// let flags: tyflags!(Red, Blue) = Or::<Red, Blue>::default();
+/// assert_eq!(flags.to_set(), Red | Blue);
+/// ```
+#[macro_export]
+macro_rules! tyflags {
+    ($expr: expr $(,)?) => {
+        $expr
+    };
+    ($first: ty, $($rest: ty),+ $(,)?) => {
+        $crate::flags_type!($first | $($rest)|+)
+    };
+}
+
+/// The type level union of flags, as [`Or`] nested to the right.
+///
+/// ```
+/// # use tlbf::*;
+/// # tlbf!(
+/// #     pub Color: u64 {
+/// #         pub Red, Green, Blue,
+/// #     }
+/// # );
+/// fn warm(flags: flags_type!(Red | Green)) -> Color {
+///     flags.to_set()
+/// }
+/// assert_eq!(warm(Default::default()), Red | Green);
+/// let _: flags_type!(Red | Green | Blue) = Or::<Red, Or<Green, Blue>>::default();
+/// ```
+#[macro_export]
+macro_rules! flags_type {
+    ($ty: ty) => {
+        $ty
+    };
+    ($first: ty | $($rest: ty)|+) => {
+        $crate::Or<$first, $crate::flags_type!($($rest)|+)>
+    };
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod test {
+    extern crate std;
+    use std::format;
+
+    tlbf!(
+        pub Unit1: u8 {
+            Hello
+        }
+    );
+    tlbf!(
+        pub Unit2: u8 {
+            Hiii,
+        }
+    );
+    #[test]
+    pub fn test(){
+        tlbf!(
+            pub Mascot: u8 {
+                pub Ferris
+            }
+        );
+        tlbf!(
+            #[derive(Default)]
+            LesserMascots: u8 {
+                #[repr(C)]
+                Gopher
+            }
+        );
+        assert_eq!(Mascot::all(), Mascot::Ferris);
+
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::all(), Colors::Red|Colors::Blue|Colors::Green);
+    }
+
+    #[test]
+    pub fn radix_fmt(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let flags = Colors::Red|Colors::Green;
+        assert_eq!(format!("{:#x}", flags), "0x5");
+        assert_eq!(format!("{:X}", Colors::all()), "7");
+        assert_eq!(format!("{:#010b}", flags), "0b00000101");
+        assert_eq!(format!("{:o}", Colors::all()), "7");
+    }
+
+    #[test]
+    pub fn flag_at(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::flag_at(0), Some(Colors::Red));
+        assert_eq!(Colors::flag_at(2), Some(Colors::Green));
+        assert_eq!(Colors::flag_at(3), None);
+        for (i, flag) in Colors::all_flags().iter().enumerate() {
+            assert_eq!(Colors::flag_at(i), Some(*flag));
+        }
+    }
+
+    #[test]
+    pub fn cfg_flags(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                #[cfg(any())]
+                pub Blue,
+                #[cfg(all())]
+                pub Green,
+            }
+        );
+        assert_eq!(format!("{:b}", Colors::Green), "100");
+        assert_eq!(format!("{:b}", Colors::all()), "101");
+        assert_eq!(Colors::all_flags(), &[Colors::Red, Colors::Green]);
+
+        tlbf!(
+            pub Explicit: u8 {
+                pub Up = 1,
+                #[cfg(any())]
+                pub Down = 2,
+                pub Left = 4,
+            }
+        );
+        assert_eq!(format!("{:b}", Explicit::all()), "10010");
+    }
+
+    #[test]
+    pub fn is_single(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert!(Colors::Red.is_single());
+        assert!(Colors::Green.is_single());
+        assert!(!(Colors::Red|Colors::Green).is_single());
+        assert!(!Colors(0).is_single());
+        assert!(!Colors(0b1000).is_single());
+        assert!(Colors(0b1010).is_single());
+    }
+
+    #[test]
+    pub fn name(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::names(), &["Red", "Blue", "Green"]);
+        assert_eq!(Colors::metadata(), &[("Red", 0, 1), ("Blue", 1, 2), ("Green", 2, 4)]);
+        assert_eq!(Colors::Blue.name(), Some("Blue"));
+        assert_eq!(Colors(0b1100).name(), Some("Green"));
+        assert_eq!((Colors::Red|Colors::Blue).name(), None);
+        assert_eq!(Colors(0).name(), None);
+        assert_eq!(Colors::from_name("Green"), Some(Colors::Green));
+        assert_eq!(Colors::from_name("green"), None);
+    }
+
+    #[test]
+    pub fn parse(){
+        use crate::ParseError;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::parse("5"), Ok(Red|Green));
+        assert_eq!(Colors::parse("0b101"), Ok(Red|Green));
+        assert_eq!(Colors::parse("0x2"), Ok(Colors::Blue));
+        assert_eq!(Colors::parse("0o7"), Ok(Colors::all()));
+        assert_eq!(Colors::parse(" Red | Green "), Ok(Red|Green));
+        assert_eq!(Colors::parse(""), Ok(Colors::EMPTY));
+        assert_eq!(Colors::parse("8"), Err(ParseError::UndefinedBits));
+        assert_eq!(Colors::parse("0b2"), Err(ParseError::InvalidNumber));
+        assert_eq!(Colors::parse("Red | Purple"), Err(ParseError::UnknownName));
+        assert_eq!(Colors::parse("Red | 0x80"), Err(ParseError::UndefinedBits));
+        assert_eq!(Colors::parse("Red | 0x4"), Ok(Red|Green));
+        {
+            tlbf!(
+                pub Signed: i8 {
+                    pub A = 6,
+                    pub B,
+                }
+            );
+            assert_eq!(Signed::parse("0x80"), Ok(Signed::B));
+            assert_eq!(Signed::parse("0b11000000"), Ok(Signed::all()));
+            assert_eq!(Signed::parse("128"), Ok(Signed::B));
+            assert_eq!(Signed::parse("0x100"), Err(ParseError::InvalidNumber));
+            assert_eq!(Signed::parse("B | 0x40"), Ok(Signed::all()));
+        }
+    }
+
+    #[test]
+    pub fn display(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(format!("{}", Red|Green), "Red | Green");
+        assert_eq!(format!("{}", Colors::EMPTY), "");
+        assert_eq!(format!("{}", Colors::from_bits_retain(0b1001)), "Red | 0x8");
+        assert_eq!("Red | Green".parse(), Ok(Red|Green));
+        assert_eq!("Red | 0x8".parse(), Ok(Colors::from_bits_retain(0b1001)));
+        assert_eq!("Red, Green".parse::<Colors>(), Err(crate::ParseError::UnknownName));
+
+        tlbf!(
+            #[tlbf(display_sep = ", ")]
+            pub Direction: u8 {
+                pub Up,
+                pub Down,
+                pub Left,
+            }
+        );
+        assert_eq!(format!("{}", Up|Left), "Up, Left");
+        assert_eq!("Up,Left".parse(), Ok(Up|Left));
+        assert_eq!(Direction::parse("Down, Left"), Ok(Down|Left));
+
+        tlbf!(
+            pub Signed: i8 {
+                pub A,
+                pub B,
+            }
+        );
+        let reserved = Signed::from_bits_retain(-128);
+        assert_eq!(format!("{}", reserved), "0x80");
+        assert_eq!(format!("{}", reserved).parse(), Ok(reserved));
+        assert_eq!(format!("{}", reserved | A).parse(), Ok(reserved | A));
+    }
+
+    #[test]
+    pub fn try_from_names(){
+        use crate::ParseError;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::try_from(&["Red", "Green"][..]), Ok(Red|Green));
+        assert_eq!(Colors::try_from(&[] as &[&str]), Ok(Colors::EMPTY));
+        assert_eq!(Colors::try_from(&["Red", "Purple"][..]), Err(ParseError::UnknownName));
+        #[cfg(feature = "alloc")]
+        {
+            use std::string::ToString;
+            let names = ["Blue".to_string(), "Red".to_string()];
+            assert_eq!(Colors::try_from(&names[..]), Ok(Red|Blue));
+        }
+    }
+
+    #[test]
+    pub fn rename(){
+        tlbf!(
+            pub Permissions: u8 {
+                pub Read,
+                /// Both read and write.
+                #[tlbf(rename = "read-write")]
+                #[cfg(all())]
+                pub ReadWrite,
+            }
+        );
+        assert_eq!(format!("{}", ReadWrite), "read-write");
+        assert_eq!(Permissions::ReadWrite.name(), Some("read-write"));
+        assert_eq!(Permissions::from_name("read-write"), Some(Permissions::ReadWrite));
+        assert_eq!(Permissions::metadata()[1], ("read-write", 1, 2));
+    }
+
+    #[test]
+    pub fn split_first(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!((Colors::Blue|Colors::Green).split_first(), Some((Colors::Blue, Colors::Green)));
+        assert_eq!(Colors::Green.split_first(), Some((Colors::Green, Colors::from_bits_retain(0))));
+        assert_eq!(Colors::from_bits_retain(0b1001).split_first(), Some((Colors::Red, Colors::from_bits_retain(0b1000))));
+        assert_eq!(Colors::from_bits_retain(0b1000).split_first(), None);
+
+        assert_eq!(Colors::from_bits_retain(0b1011).highest(), Some(Colors::Blue));
+        assert_eq!(Colors::from_bits_retain(0b1110).lowest(), Some(Colors::Blue));
+        assert_eq!(Colors::from_bits_retain(0b1000).highest(), None);
+        assert_eq!(Colors::EMPTY.lowest(), None);
+
+        let mut flags = Colors::from_bits_retain(0b1101);
+        assert_eq!(flags.pop(), Some(Colors::Red));
+        assert_eq!(flags.pop(), Some(Colors::Green));
+        assert_eq!(flags.pop(), None);
+        assert_eq!(flags, Colors::from_bits_retain(0b1000));
+    }
+
+    #[test]
+    pub fn next_single(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::Red.next_single(), Some(Colors::Blue));
+        assert_eq!(Colors::Blue.next_single(), Some(Colors::Green));
+        assert_eq!(Colors::Green.next_single(), Some(Colors::Red));
+        assert_eq!(Colors::all().next_single(), None);
+        assert_eq!(Colors(0).next_single(), None);
+    }
+
+    #[test]
+    pub fn invert_within(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let flags = Colors::Red|Colors::Green;
+        assert_eq!(flags.invert_within(Colors::Red|Colors::Blue), Colors::Blue|Colors::Green);
+        assert_eq!(flags.invert_within(Colors::Green), Colors::Red);
+        assert_eq!(Colors(0b1000).invert_within(Colors::Red), Colors(0b1001));
+
+        assert_eq!(Colors::EMPTY.merge_if(true, Red).merge_if(false, Blue), Colors::Red);
+
+        let mut flags = Colors(0b1001);
+        flags.toggle_all();
+        assert_eq!(flags, Colors(0b1110));
+    }
+
+    #[test]
+    pub fn const_ops(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        const ALL_RED: bool = Colors::all().contains_const(Colors::Red);
+        const RED_ALL: bool = Colors::Red.contains_const(Colors::all());
+        const _: () = assert!(ALL_RED && !RED_ALL);
+        const _: () = assert!(Colors::all().intersects_const(Colors::Blue));
+        const _: () = assert!(!Colors::Red.intersects_const(Colors::Blue));
+        const RAW: u8 = Colors::Green.bits();
+        const _: () = assert!(RAW == 4);
+        const DEFAULT: Colors = Colors::from_bits_retain(0b011);
+        const ALLOWED: Colors = Colors::from_bits_retain(0b111);
+        const _: () = assert!(DEFAULT.is_subset_const(ALLOWED));
+        const ALL: [Colors; 3] = Colors::ALL_FLAGS;
+        const _: () = assert!(ALL[2].bits() == 4);
+        const _: () = assert!(!ALLOWED.is_subset_const(DEFAULT));
+
+        const TABLE: [Colors; 3] = [Colors::from_index(0), Colors::from_index(2), Colors::from_index(8)];
+        assert_eq!(TABLE, [Colors::Red, Colors::Green, Colors::from_bits_retain(0)]);
+
+        let describe = |flags: Colors| match flags {
+            Colors::EMPTY => "none",
+            Colors::ALL => "all",
+            _ => "some",
+        };
+        assert_eq!(describe(Colors::EMPTY), "none");
+        assert_eq!(describe(Red|Blue|Green), "all");
+        assert_eq!(describe(Colors::Red), "some");
+    }
+
+    #[test]
+    pub fn tuples(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let flags = Colors::Red|Colors::Blue;
+        assert!(flags.contains((Red, Blue)));
+        assert!(flags.contains((Red,)));
+        assert!(!flags.contains((Red, Blue, Green)));
+        assert!(flags.equals((Blue, Red)));
+        assert_eq!(Colors::Green | (Red, Blue), Colors::all());
+    }
+
+    #[test]
+    pub fn references(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let red = &Red;
+        let flags = Colors::Red|Colors::Blue;
+        assert!(flags.contains(red));
+        assert!([Red, Red].iter().all(|member| flags.contains(member)));
+        assert_eq!(Colors::Green | red, Red|Green);
+        assert!(Red == red);
+    }
+
+    #[test]
+    pub fn not(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(!Red, Blue|Green);
+        assert!((!Green).contains(Blue));
+        assert!(!(!Green).contains(Green));
+    }
+
+    #[test]
+    pub fn matches_exactly(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let flags = Colors(0b1000_0011);
+        assert!(!flags.equals(Red|Blue));
+        assert!(flags.matches_exactly(Red|Blue));
+        assert!(!flags.matches_exactly(Red));
+        assert_eq!(flags.normalized(), Colors::Red|Colors::Blue);
+    }
+
+    #[test]
+    pub fn default_option(){
+        tlbf!(
+            #[tlbf(default = Blue)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::default(), Colors::Blue);
+
+        tlbf!(
+            #[derive(PartialOrd)]
+            #[tlbf(default = (Up, Left))]
+            pub Direction: u8 {
+                pub Up,
+                pub Down,
+                pub Left,
+            }
+        );
+        assert_eq!(Direction::default(), Direction::Up|Direction::Left);
+    }
+
+    #[test]
+    pub fn transparent_ord(){
+        tlbf!(
+            #[tlbf(transparent_ord)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert!(Colors::Red < Colors::Blue);
+        assert!(Colors::Green < Red|Green);
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(Colors::Green, 1);
+        map.insert(Red|Blue, 2);
+        assert_eq!(map.keys().copied().collect::<std::vec::Vec<_>>(), [Red|Blue, Colors::Green]);
+    }
+
+    #[test]
+    pub fn signed(){
+        tlbf!(
+            pub Signed: i8 {
+                pub A = 6,
+                pub B,
+            }
+        );
+        assert!(Signed::all().intersects(Signed::B));
+        assert!(Signed::B.intersects_const(Signed::all()));
+        assert!(!Signed::A.intersects(Signed::B));
+        assert_eq!(Signed::all().highest(), Some(Signed::B));
+        assert_eq!(Signed::all().lowest(), Some(Signed::A));
+        assert!(Signed::B.is_single());
+        assert!(!Signed::all().is_single());
+        assert_eq!(Signed::B.name(), Some("B"));
+        assert_eq!(Signed::B.next_single(), Some(Signed::A));
+        assert_eq!(Signed::A.next_single(), Some(Signed::B));
+        assert_eq!(Signed::B.try_single(), Ok(Signed::B));
+        assert_eq!(Signed::all().try_single(), Err(crate::SingleFlagError::Multiple));
+    }
+
+    #[test]
+    pub fn compat(){
+        mod other {
+            tlbf!(
+                pub Colors: u8 {
+                    pub Red,
+                    pub Blue,
+                    pub Green,
+                }
+            );
+        }
+        tlbf!(
+            #[tlbf(compat = other::Colors)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::from(other::Colors::Red|other::Colors::Green), Red|Green);
+        assert_eq!(other::Colors::from(Colors::Blue), other::Colors::Blue);
+    }
+
+    #[test]
+    pub fn bytes(){
+        tlbf!(
+            pub Colors: u16 {
+                pub Red,
+                pub Blue = 9,
+                pub Green,
+            }
+        );
+        let flags = Colors::Red|Colors::Green;
+        assert_eq!(flags.to_le_bytes(), [0b1, 0b100]);
+        assert_eq!(flags.to_be_bytes(), [0b100, 0b1]);
+        assert_eq!(Colors::from_le_bytes([0b1, 0b100]), Some(flags));
+        assert_eq!(Colors::from_be_bytes([0b100, 0b1]), Some(flags));
+        assert_eq!(Colors::from_ne_bytes(flags.to_ne_bytes()), Some(flags));
+        assert_eq!(Colors::from_le_bytes([0b11, 0b100]), None);
+        assert_eq!(Colors::from_le_bytes_truncate([0b11, 0b100]), flags);
+        assert_eq!(Colors::REPR_BITS, 16);
+        assert_eq!(Colors::COUNT, 3);
+    }
+
+    #[test]
+    pub fn exclusive(){
+        tlbf!(
+            pub Style: u8 {
+                pub Red,
+                pub Green,
+                pub Blue,
+                pub Bold,
+                pub Italic,
+            }
+            exclusive Color { Red, Green, Blue }
+            exclusive Font { Bold, Italic, }
+        );
+        let mut style = Style::Red;
+        style.set_exclusive(Bold);
+        assert_eq!(style, Red|Bold);
+        style.set_exclusive(Green|Italic);
+        assert_eq!(style, Green|Italic);
+        style.set_exclusive(Style::Blue);
+        assert_eq!(style, Blue|Italic);
+        assert!(style.is_valid());
+        assert!((Red|Bold).is_valid());
+        assert!(Style::from_bits_retain(0).is_valid());
+        assert!(!Style::from_bits_retain(0b11000).is_valid());
+        assert!((Red|Bold).exactly_one_of(Red|Green|Blue));
+        assert!(!(Red|Green).exactly_one_of(Style::Color));
+        assert!(!Style::Bold.exactly_one_of(Style::Color));
+        assert_eq!((Red|Blue|Bold).count_within(Style::Color), 2);
+        assert_eq!((Red|Blue|Bold).count_within(Bold|Italic), 1);
+    }
+
+    #[test]
+    pub fn aliases(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                /// Both red and blue.
+                pub const Purple = Red | Blue,
+                pub Green,
+                #[cfg(any())]
+                pub const Missing = Red,
+            }
+        );
+        assert_eq!(Colors::Purple, Red|Blue);
+        assert_eq!(Colors::Green, Colors::from_bits_retain(0b100));
+        assert_eq!(Colors::all(), Red|Blue|Green);
+        assert_eq!(Colors::COUNT, 3);
+        assert_eq!(Colors::names(), &["Red", "Blue", "Green"]);
+        assert_eq!(Colors::all_flags(), &[Colors::Red, Colors::Blue, Colors::Green]);
+        assert_eq!(Colors::Purple.name(), None);
+    }
+
+    #[test]
+    pub fn set_bits(){
+        use crate::SetMember;
+        fn popcount<T: SetMember>(member: T) -> u32 where T::Set: crate::Bits {
+            member.set_bits().count_ones()
+        }
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Red.set_bits(), 1);
+        assert_eq!((Red|Green).set_bits(), 0b101);
+        assert_eq!(popcount(Colors::all()), 3);
+        assert_eq!(popcount((Blue, Green)), 2);
+        assert!(!Red.is_empty_set());
+        assert!(Colors::EMPTY.is_empty_set());
+        assert!((Colors::EMPTY, Colors::EMPTY).is_empty_set());
+        {
+            tlbf!(
+                pub Signed: i8 {
+                    pub A = 6,
+                    pub B,
+                }
+            );
+            assert_eq!(B.set_bits(), 0x80);
+            assert_eq!(popcount(Signed::all()), 2);
+        }
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    pub fn borsh(){
+        tlbf!(
+            pub Colors: u16 {
+                pub Red,
+                pub Blue = 9,
+                pub Green,
+            }
+        );
+        let flags = Colors::Red|Colors::Green;
+        let bytes = borsh::to_vec(&flags).unwrap();
+        assert_eq!(bytes, [0b1, 0b100]);
+        assert_eq!(borsh::from_slice::<Colors>(&bytes).unwrap(), flags);
+        assert!(borsh::from_slice::<Colors>(&[0b11, 0b100]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde(){
+        tlbf!(
+            #[tlbf(default = Red | Green)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(default)]
+            colors: Colors,
+        }
+        let json = serde_json::to_string(&Config { colors: Red | Blue }).unwrap();
+        assert_eq!(json, r#"{"colors":"Red | Blue"}"#);
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap().colors, Red | Blue);
+        assert_eq!(serde_json::from_str::<Config>("{}").unwrap().colors, Red | Green);
+        assert_eq!(serde_json::from_str::<Config>(r#"{"colors":""}"#).unwrap().colors, Colors::EMPTY);
+        assert!(serde_json::from_str::<Config>(r#"{"colors":"Red | 0x80"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"colors":"Purple"}"#).is_err());
+    }
+
+    #[test]
+    pub fn fold_flags(){
+        use crate::IterFlagsExt;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let sets = [Colors::Red, Colors::Blue, Colors::Red];
+        assert_eq!(sets.iter().copied().fold_flags(), Red|Blue);
+        assert_eq!(sets.iter().filter(|x| **x != Colors::Red).copied().fold_flags(), Colors::Blue);
+        assert_eq!([Red|Green].into_iter().fold_flags(), Red|Green);
+    }
+
+    #[test]
+    pub fn flag_repr(){
+        use crate::FlagRepr;
+        fn ones<R: FlagRepr>(bits: R) -> u32 {
+            bits.count_ones()
+        }
+        assert_eq!(ones(0b1011u8), 3);
+        assert_eq!(<u128 as FlagRepr>::BITS, 128);
+        assert_eq!(<i16 as FlagRepr>::from_str_radix("ff", 16), Ok(255));
+        assert_eq!(<i8 as FlagRepr>::from_str_radix("80", 16), Ok(i8::MIN));
+        assert!(<i8 as FlagRepr>::from_str_radix("-1", 10).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn json_array(){
+        use crate::ParseError;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                #[tlbf(rename = "gr\"een")]
+                pub Green,
+            }
+        );
+        assert_eq!((Red|Blue).to_json_array(), r#"["Red","Blue"]"#);
+        assert_eq!(Colors::EMPTY.to_json_array(), "[]");
+        assert_eq!(Colors::from_bits_retain(0x80).to_json_array(), "[]");
+        assert_eq!(Colors::from_json_array(&Colors::ALL.to_json_array()), Ok(Colors::ALL));
+        assert_eq!(Colors::from_json_array(r#" [ "Blue" , "Red" ] "#), Ok(Red|Blue));
+        assert_eq!(Colors::from_json_array(" [ ] "), Ok(Colors::EMPTY));
+        assert_eq!(Colors::from_json_array(r#"["Purple"]"#), Err(ParseError::UnknownName));
+        assert_eq!(Colors::from_json_array(r#"["Red",]"#), Err(ParseError::InvalidSyntax));
+        assert_eq!(Colors::from_json_array(r#"["Red""#), Err(ParseError::InvalidSyntax));
+        assert_eq!(Colors::from_json_array("Red"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[test]
+    pub fn with_bit(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue = 4,
+                pub Green,
+            }
+        );
+        assert_eq!(Red.bit_index(), 0);
+        assert_eq!(Green.bit_index(), 5);
+        assert_eq!(Colors::EMPTY.with_bit(Blue.bit_index(), true), Colors::Blue);
+        assert_eq!(Colors::ALL.with_bit(5, false), Red|Blue);
+        assert_eq!(Colors::from(Red).with_bit(0, true), Colors::Red);
+        assert!(std::panic::catch_unwind(|| Colors::EMPTY.with_bit(1, true)).is_err());
+        assert!(std::panic::catch_unwind(|| Colors::EMPTY.with_bit(8, true)).is_err());
+    }
+
+    #[test]
+    pub fn ignore_reserved_bits(){
+        use std::collections::HashMap;
+        tlbf!(
+            #[tlbf(ignore_reserved_bits)]
+            #[tlbf(transparent_ord)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let reserved = Colors::from_bits_retain(0b1000_0001);
+        assert_eq!(reserved, Colors::Red);
+        assert_eq!(reserved.cmp(&Colors::Red), std::cmp::Ordering::Equal);
+        assert_ne!(reserved, Colors::Blue);
+        let mut map = HashMap::new();
+        map.insert(Colors::Red, 1);
+        map.insert(reserved, 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[&Colors::Red], 2);
+    }
+
+    #[test]
+    pub fn contained_in(){
+        use crate::SetMember;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let set = Red | Blue;
+        assert!(Red.contained_in(&set));
+        assert!(!Green.contained_in(&set));
+        assert!((Red, Blue).contained_in(&set));
+        assert!(set.contained_in(&Colors::ALL));
+        assert!(!Colors::ALL.contained_in(&set));
+        assert_eq!(Red.contained_in(&set), set.contains(Red));
+    }
+
+    #[test]
+    pub fn sub(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Red - Blue, Colors::Red);
+        assert_eq!(Red - Red, Colors::EMPTY);
+        assert_eq!(Red - (Red | Blue), Colors::EMPTY);
+        assert_eq!(Colors::ALL - Red, Blue | Green);
+        assert_eq!(Colors::from_bits_retain(0x81) - Red, Colors::from_bits_retain(0x80));
+        let mut colors = Colors::ALL;
+        colors -= (Blue, Green);
+        assert_eq!(colors, Colors::Red);
+    }
+
+    #[test]
+    pub fn clear_within(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let mut colors = Colors::from_bits_retain(0x87);
+        colors.clear_within(Red | Green);
+        assert_eq!(colors, Colors::from_bits_retain(0x82));
+        colors.clear_within(crate::Or::<Blue, Green>::default());
+        assert_eq!(colors, Colors::from_bits_retain(0x80));
+    }
+
+    #[test]
+    pub fn debug(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        tlbf!(
+            #[tlbf(debug_hex)]
+            pub HexColors: u16 {
+                pub HexRed,
+                pub HexBlue,
+            }
+        );
+        tlbf!(
+            #[tlbf(debug_names)]
+            pub NamedColors: u8 {
+                pub NamedRed,
+                pub NamedBlue,
+                pub NamedGreen,
+            }
+        );
+        assert_eq!(format!("{:?}", Red | Green), "Colors(5)");
+        assert_eq!(format!("{:?}", HexRed | HexBlue), "HexColors(0x0003)");
+        assert_eq!(format!("{:?}", NamedRed | NamedGreen), "NamedColors(NamedRed | NamedGreen)");
+        assert_eq!(format!("{:?}", NamedColors::from_bits_retain(0x81)), "NamedColors(NamedRed | 0x80)");
+        assert_eq!(format!("{:?}", NamedColors::EMPTY), "NamedColors(0x0)");
+    }
+
+    #[test]
+    pub fn iter_indices(){
+        use std::vec::Vec;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue = 3,
+                pub Green = 7,
+            }
+        );
+        assert_eq!((Red | Green).iter_indices().collect::<Vec<_>>(), [0, 7]);
+        assert_eq!(Colors::from_bits_retain(0xff).iter_indices().collect::<Vec<_>>(), [0, 3, 7]);
+        assert_eq!(Colors::EMPTY.iter_indices().next(), None);
+        tlbf!(
+            pub Signed: i8 {
+                pub Low,
+                pub High = 7,
+            }
+        );
+        assert_eq!(Signed::ALL.iter_indices().collect::<Vec<_>>(), [0, 7]);
+    }
+
+    #[test]
+    pub fn try_single(){
+        use crate::SingleFlagError;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::Blue.try_single(), Ok(Colors::Blue));
+        assert_eq!(Colors::from_bits_retain(0x82).try_single(), Ok(Colors::Blue));
+        assert_eq!(Colors::EMPTY.try_single(), Err(SingleFlagError::Empty));
+        assert_eq!(Colors::from_bits_retain(0x80).try_single(), Err(SingleFlagError::Empty));
+        assert_eq!((Red | Green).try_single(), Err(SingleFlagError::Multiple));
+        assert_eq!(format!("{}", SingleFlagError::Multiple), "multiple flags set");
+    }
+
+    #[test]
+    pub fn union_all(){
+        use std::vec;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::union_all([Colors::Red, Blue | Green]), Colors::ALL);
+        assert_eq!(Colors::union_all(vec![Colors::Red, Colors::Red]), Colors::Red);
+        assert_eq!(Colors::union_all([]), Colors::EMPTY);
+    }
+
+    #[test]
+    pub fn intersection_all(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::intersection_all([Red | Blue, Colors::ALL, Blue | Green]), Colors::Blue);
+        assert_eq!(Colors::intersection_all([Red | Blue, Colors::Green]), Colors::EMPTY);
+        assert_eq!(Colors::intersection_all([Colors::from_bits_retain(0x81)]), Colors::Red);
+        assert_eq!(Colors::intersection_all([]), Colors::all());
+    }
+
+    #[test]
+    pub fn is_disjoint(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let colors = Red | Blue;
+        assert!(colors.is_disjoint(Green));
+        assert!(!colors.is_disjoint((Blue, Green)));
+        assert!(Colors::EMPTY.is_disjoint(Colors::ALL));
+        assert!(colors.is_exclusive_with(Green));
+        assert!(!colors.is_exclusive_with(Red));
+    }
+
+    #[test]
+    pub fn modules(){
+        tlbf!(
+            pub mod light {
+                pub Light: u8 {
+                    pub Red,
+                    pub Green,
+                }
+            }
+        );
+        tlbf!(
+            mod paint {
+                #[tlbf(default = Blue)]
+                pub Paint: u16 {
+                    pub Red,
+                    pub Blue,
+                }
+            }
+        );
+        assert_eq!(light::Red | light::Green, light::Light::ALL);
+        assert_eq!(paint::Paint::default(), paint::Paint::Blue);
+        assert_eq!(paint::Paint::Red.bits(), 1);
+    }
+
+    #[test]
+    pub fn empty(){
+        use crate::{Empty, SetMember};
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let empty = Empty::<Colors>::default();
+        assert_eq!(empty.to_set(), Colors::EMPTY);
+        assert!(Colors::EMPTY.contains(empty));
+        assert!(Colors::EMPTY.equals(empty));
+        assert!(!Colors::Red.equals(empty));
+        assert_eq!(Red | Blue | empty, Red | Blue);
+    }
+
+    #[test]
+    pub fn expose_raw(){
+        tlbf!(
+            #[tlbf(expose_raw)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let mut colors = Colors::Red;
+        *colors.bits_mut() |= 0b1010;
+        assert_eq!(colors.bits(), 0b1011);
+        assert_eq!(colors.normalized(), Red | Blue);
+    }
+
+    #[test]
+    pub fn eq_repr(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        tlbf!(
+            #[tlbf(ignore_reserved_bits)]
+            pub Masked: u8 {
+                pub MaskedRed,
+            }
+        );
+        assert_eq!(Red | Green, 5);
+        assert_eq!(5, Red | Green);
+        assert_ne!(Colors::from_bits_retain(0x81), 1);
+        assert_eq!(Masked::from_bits_retain(0x81), 1);
+        assert_eq!(1, Masked::from_bits_retain(0x81));
+    }
+
+    #[test]
+    pub fn widen(){
+        tlbf!(
+            mod wide {
+                pub Colors: i16 {
+                    pub Red,
+                    pub Blue,
+                    pub Green = 7,
+                    pub Alpha,
                 }
+            }
+        );
+        tlbf!(
+            #[tlbf(widen = wide::Colors)]
+            pub Colors: i8 {
+                pub Red,
+                pub Blue,
+                pub Green = 7,
+            }
+        );
+        assert_eq!(wide::Colors::from(Red | Green), wide::Red | wide::Green);
+        assert_eq!(wide::Colors::from(Colors::from_bits_retain(0x44)).bits(), 0x44);
+        assert_eq!(wide::Colors::from(Colors::EMPTY), wide::Colors::EMPTY);
+    }
+
+    #[test]
+    pub fn predicates(){
+        use crate::{all_of, any_of, FlagPredicate};
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert!(any_of([Colors::Red, Colors::Blue]).test(&Colors::Blue));
+        assert!(!any_of([Colors::Red, Colors::Blue]).test(&Colors::Green));
+        assert!(all_of([Red | Blue, Colors::Green]).test(&Colors::ALL));
+        assert!(!all_of([Red | Blue, Colors::Green]).test(&(Red | Green)));
+        assert!(!any_of::<Colors, 0>([]).test(&Colors::ALL));
+        assert!(all_of::<Colors, 0>([]).test(&Colors::EMPTY));
+        let rule = any_of([all_of([Colors::Red, Colors::Blue]), all_of([Colors::Green, Colors::Green])]);
+        assert!(rule.test(&(Red | Blue)));
+        assert!(rule.test(&Colors::Green));
+        assert!(!rule.test(&Colors::Red));
+    }
+
+    #[test]
+    pub fn bools(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue = 4,
+                #[cfg(any())]
+                pub Yellow,
+                pub Green,
+            }
+        );
+        assert_eq!((Red | Green).into_bools(), [true, false, true]);
+        assert_eq!(Colors::from([false, true, true]), Blue | Green);
+        assert_eq!(Colors::from(Colors::ALL.into_bools()), Colors::ALL);
+        assert_eq!(Colors::from_bits_retain(0x82).into_bools(), [false; 3]);
+    }
+
+    #[test]
+    pub fn retain(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let mut colors = Colors::from_bits_retain(0x87);
+        colors.retain(|flag| flag != Colors::Blue);
+        assert_eq!(colors, Colors::from_bits_retain(0x85));
+        let mut colors = Colors::ALL;
+        colors.retain_within(Blue | Green, |_| false);
+        assert_eq!(colors, Colors::Red);
+        let mut colors = Colors::ALL;
+        let mut seen = 0;
+        colors.retain_within(Red | Blue, |flag| {
+            seen += 1;
+            flag == Colors::Red
+        });
+        assert_eq!(seen, 2);
+        assert_eq!(colors, Red | Green);
+    }
+
+    #[test]
+    pub fn difference_count(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!((Red | Blue).difference_count(&(Blue | Green)), 2);
+        assert_eq!(Colors::ALL.difference_count(&Colors::ALL), 0);
+        assert_eq!(Colors::EMPTY.difference_count(&Colors::ALL), 3);
+        assert_eq!(Colors::from_bits_retain(0x80).difference_count(&Colors::EMPTY), 0);
+    }
+
+    #[test]
+    pub fn bit_ranges(){
+        tlbf!(
+            pub Register: i16 {
+                pub Enable,
+                pub Ready,
+                pub const Low = bits(0..2),
+                #[cfg(all())]
+                pub const Reserved = bits(8..16),
+                pub const Nothing = bits(3..3),
+                pub Error,
+            }
+        );
+        assert_eq!(Register::Low, Enable | Ready);
+        assert_eq!(Register::Reserved.bits(), 0xff00u16 as i16);
+        assert_eq!(Register::Nothing, Register::EMPTY);
+        assert_eq!(Register::Error.bits(), 4);
+        assert_eq!(Register::COUNT, 3);
+        let mut register = Register::Enable;
+        register |= Register::Reserved;
+        register ^= Register::Low;
+        assert_eq!(register, Register::Ready | Register::Reserved);
+    }
+
+    #[test]
+    pub fn add_ops(){
+        tlbf!(
+            #[tlbf(add_ops)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+                pub const High = bits(6..8),
+            }
+        );
+        assert_eq!(Colors::Red + Blue, Red | Blue);
+        assert_eq!(Colors::Red + Red, Colors::Red);
+        let mut colors = Colors::EMPTY;
+        colors += Green;
+        colors += (Red, Green);
+        assert_eq!(colors, Red | Green);
+        colors += Colors::High;
+        assert_eq!(colors.bits(), 0b1100_0101);
+    }
+
+    #[test]
+    pub fn wrappers(){
+        use crate::SetMember;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        struct Palette(Colors);
+        struct Primary {
+            color: Red,
+        }
+        impl_set_member!(Palette(Colors));
+        impl_set_member!(Primary { color: Red });
+        let palette = Palette(Red | Blue);
+        assert_eq!(palette.to_set(), Red | Blue);
+        assert!((Red | Blue).equals(&palette));
+        assert!(palette.0.contains(Primary { color: Red }));
+        assert_eq!(Colors::Green | Primary { color: Red }, Red | Green);
+
+        mod nested {
+            pub struct Tagged<T>(pub u32, pub T);
+        }
+        struct Either<A, B> {
+            left: A,
+            #[allow(dead_code)]
+            right: B,
+        }
+        impl_set_member!(impl[T] nested::Tagged<T> { 1: T } where T: SetMember);
+        impl_set_member!(impl[A: SetMember, B] Either<A, B> { left: A });
+        let tagged = nested::Tagged(0, Either { left: Palette(Red | Green), right: () });
+        assert_eq!(tagged.to_set(), Red | Green);
+        assert!(Colors::ALL.contains(&tagged));
+    }
+
+    #[test]
+    pub fn flags_type(){
+        use crate::SetMember;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        fn to_colors(flags: flags_type!(Red | Blue | Green)) -> Colors {
+            flags.to_set()
+        }
+        assert_eq!(to_colors(Default::default()), Colors::ALL);
+        let single: flags_type!(Blue) = Blue;
+        assert_eq!(single.to_set(), Colors::Blue);
+        let list: tyflags!(Red, Blue, Green) = Default::default();
+        let _: flags_type!(Red | Blue | Green) = list;
+        assert_eq!(list.to_set(), Colors::ALL);
+    }
+
+    #[test]
+    pub fn iter_names_sorted(){
+        use std::vec::Vec;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                #[tlbf(rename = "amber")]
+                pub Amber,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::ALL.iter_names_sorted().collect::<Vec<_>>(), ["Blue", "Green", "Red", "amber"]);
+        assert_eq!((Red | Green).iter_names_sorted().collect::<Vec<_>>(), ["Green", "Red"]);
+        assert_eq!(Colors::from_bits_retain(0x80).iter_names_sorted().next(), None);
+    }
+
+    #[test]
+    pub fn clear_and_set(){
+        use crate::Or;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let mut colors = Colors::from_bits_retain(0x83);
+        colors.clear_and_set(Blue | Green, Green);
+        assert_eq!(colors, Colors::from_bits_retain(0x85));
+        colors.clear_and_set(Or::<Red, Blue>::default(), Or::<Blue, Green>::default());
+        assert_eq!(colors, Colors::from_bits_retain(0x86));
+    }
+
+    #[test]
+    pub fn from_name_large(){
+        tlbf!(
+            pub Large: u64 {
+                pub F0, pub F1, pub F2, pub F3, pub F4, pub F5, pub F6, pub F7, pub F8, pub F9, pub F10, pub F11, pub F12, pub F13, pub F14, pub F15, pub F16, pub F17, pub F18, pub F19, pub F20, pub F21, pub F22, pub F23, pub F24, pub F25, pub F26, pub F27, pub F28, pub F29, pub F30, pub F31, pub F32, pub F33, pub F34, pub F35, pub F36, pub F37, pub F38, pub F39, pub F40, pub F41, pub F42, pub F43, pub F44, pub F45, pub F46, pub F47, pub F48, pub F49, pub F50, pub F51, pub F52, pub F53, pub F54, pub F55, pub F56, pub F57, pub F58, pub F59,
+            }
+        );
+        for (index, name) in Large::names().iter().enumerate() {
+            assert_eq!(Large::from_name(name), Large::flag_at(index));
+        }
+        assert_eq!(Large::from_name("F60"), None);
+        assert_eq!("F0 | F59".parse::<Large>(), Ok(F0 | F59));
+    }
+
+    #[test]
+    pub fn truncate_to(){
+        use crate::Or;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let colors = Colors::from_bits_retain(0x83);
+        assert_eq!(colors.truncate_to(Blue | Green), Colors::Blue);
+        assert_eq!(colors.truncate_to(Or::<Red, Green>::default()), Colors::Red);
+        assert_eq!(colors.truncate_to(Colors::EMPTY), Colors::EMPTY);
+    }
+
+    #[test]
+    pub fn highest_lowest_index(){
+        tlbf!(
+            pub Colors: i8 {
+                pub Red = 1,
+                pub Blue,
+                pub Green = 7,
+            }
+        );
+        assert_eq!(Colors::ALL.highest_index(), Some(7));
+        assert_eq!(Colors::ALL.lowest_index(), Some(1));
+        assert_eq!(Colors::Blue.highest_index(), Some(2));
+        assert_eq!(Colors::from_bits_retain(0b1001).lowest_index(), None);
+        assert_eq!(Colors::EMPTY.highest_index(), None);
+    }
+
+    #[test]
+    pub fn combine(){
+        use crate::SetOp;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let colors = Red | Blue;
+        assert_eq!(colors.combine(SetOp::Union, Blue | Green), Colors::ALL);
+        assert_eq!(colors.combine(SetOp::Intersection, Blue | Green), Colors::Blue);
+        assert_eq!(colors.combine(SetOp::Difference, Blue | Green), Colors::Red);
+        assert_eq!(colors.combine(SetOp::SymmetricDifference, Blue | Green), Red | Green);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    pub fn deprecated(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                #[deprecated]
+                pub Legacy,
+                pub Blue,
+            }
+        );
+        tlbf!(
+            #[tlbf(skip_deprecated)]
+            pub Skipped: u8 {
+                pub SkippedRed,
+                #[deprecated]
+                pub SkippedLegacy,
+                pub SkippedBlue,
+            }
+        );
+        assert_eq!(Colors::COUNT, 3);
+        assert_eq!(Colors::ALL, Red | Legacy | Blue);
+        assert_eq!(Skipped::COUNT, 2);
+        assert_eq!(Skipped::ALL, SkippedRed | SkippedBlue);
+        assert_eq!(Skipped::names(), ["SkippedRed", "SkippedBlue"]);
+        assert_eq!(Skipped::ALL_FLAGS, [Skipped::SkippedRed, Skipped::SkippedBlue]);
+        assert_eq!(Skipped::from_bits(0b111), Some(SkippedRed | SkippedLegacy | SkippedBlue));
+        assert_eq!(Skipped::from_bits(0b1000), None);
+        assert_eq!(Skipped::from_name("SkippedLegacy"), Some(Skipped::SkippedLegacy));
+        assert_eq!(Skipped::SkippedLegacy.bits(), 0b10);
+    }
 
-                impl ::core::convert::From<&$name> for $flags_name {
-                    fn from(_: &$name) -> Self {
-                        Self::$name
-                    }
-                }
-    
-                impl $crate::SetMember for $name {
-                    type Set = $flags_name;
-                    fn to_set(&self) -> Self::Set {
-                        self.into()
-                    }
-                    fn eq_set(&self, set: &Self::Set) -> bool {
-                        set == &Self::Set::$name
-                    }
-                    fn in_set(&self, set: &Self::Set) -> bool {
-                        *set & Self::Set::$name == Self::Set::$name
-                    }
-                }
-    
-                impl<T> ::core::ops::BitOr<T> for $name where T: SetMember<Set=$flags_name>{
-                    type Output = $flags_name;
-                    fn bitor(self, rhs: T) -> $flags_name {
-                        $flags_name::$name | rhs.to_set()
-                    }
-                }
+    #[test]
+    pub fn kind(){
+        tlbf!(
+            #[tlbf(kind = ColorKind)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                #[cfg(any())]
+                pub Yellow,
+                pub Green,
+            }
+        );
+        assert_eq!(Colors::Blue.as_kind(), Some(ColorKind::Blue));
+        assert_eq!(Colors::from_bits_retain(0x88).as_kind(), Some(ColorKind::Green));
+        assert_eq!((Red | Blue).as_kind(), None);
+        assert_eq!(Colors::EMPTY.as_kind(), None);
+        assert_eq!(Colors::from(ColorKind::Green), Colors::Green);
+        assert!((Red | Blue).contains(ColorKind::Red));
+        assert_eq!(Red | ColorKind::Blue, Red | Blue);
+    }
 
-                impl<T> ::core::cmp::PartialEq<T> for $name where T: $crate::SetMember<Set=$flags_name>{
-                    fn eq(&self, other: &T) -> bool {
-                        $flags_name::$name == other.to_set()
-                    }
+    #[test]
+    pub fn contains_only(){
+        use crate::Or;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert!(Colors::Red.contains_only(Red | Blue));
+        assert!((Red | Blue).contains_only(Or::<Red, Blue>::default()));
+        assert!(!(Red | Green).contains_only(Red | Blue));
+        assert!(Colors::EMPTY.contains_only(Colors::EMPTY));
+        assert!(!Colors::from_bits_retain(0x81).contains_only(Colors::ALL));
+    }
+
+    #[test]
+    pub fn names_iter(){
+        use std::vec::Vec;
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                #[tlbf(rename = "green")]
+                pub Green,
+            }
+        );
+        assert_eq!((Green | Red).names_iter().collect::<Vec<_>>(), ["Red", "green"]);
+        assert_eq!(Colors::from_bits_retain(0x80).names_iter().count(), 0);
+    }
+
+    #[test]
+    pub fn rotate(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Green = 5,
+                pub Red = 0,
+                pub Blue,
+            }
+        );
+        assert_eq!(Colors::Red.rotate(1), Colors::Blue);
+        assert_eq!(Colors::Blue.rotate(1), Colors::Green);
+        assert_eq!(Colors::Green.rotate(1), Colors::Red);
+        assert_eq!(Colors::Red.rotate(-1), Colors::Green);
+        assert_eq!((Red | Green).rotate(4), Red | Blue);
+        assert_eq!(Colors::from_bits_retain(0x81).rotate(3), Colors::from_bits_retain(0x81));
+        assert_eq!(Colors::ALL.rotate(i32::MIN), Colors::ALL);
+    }
+
+    #[test]
+    pub fn as_set(){
+        use crate::{Or, SetMember};
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let colors = Red | Blue;
+        assert_eq!(colors.as_set(), Some(&colors));
+        assert_eq!(SetMember::as_set(&&colors), Some(&colors));
+        assert_eq!(Red.as_set(), None);
+        assert_eq!(Or::<Red, Blue>::default().as_set(), None);
+    }
+
+    #[test]
+    pub fn and(){
+        use crate::{And, SetMember};
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let both = And::<Red, Blue>::default();
+        assert_eq!(both.to_set(), Red | Blue);
+        assert!(both.in_set(&Colors::ALL));
+        assert!(!both.in_set(&Colors::Red));
+        assert!((Red | Blue).equals(And::<Red, Blue>::default()));
+        let all: ty_and!(Red, Blue, Green) = Default::default();
+        assert_eq!(all.to_set(), Colors::ALL);
+        assert!(!(Red | Blue).contains(all));
+    }
+
+    #[test]
+    pub fn common_bits(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!((Red | Blue).common_bits(Blue | Green), 0b010);
+        assert_eq!(Colors::from_bits_retain(0x81).common_bits(Colors::from_bits_retain(0x80)), 0x80);
+        assert_eq!(Colors::Red.common_bits(Blue), 0);
+    }
+
+    #[test]
+    pub fn entries(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub const Purple = Red | Blue,
+                #[tlbf(rename = "green")]
+                pub Green,
+            }
+        );
+        const ENTRIES: [(&str, Colors); 3] = Colors::ENTRIES;
+        const _: () = assert!(ENTRIES[2].1.bits() == 4);
+        assert_eq!(ENTRIES, [("Red", Colors::Red), ("Blue", Colors::Blue), ("green", Colors::Green)]);
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic = "rhs carries undefined bits")]
+    pub fn assign_undefined_bits(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let mut colors = Colors::from_bits_retain(0x80);
+        colors |= Red;
+        colors ^= Blue;
+        colors &= Colors::from_bits_retain(0x83);
+        colors -= Colors::from_bits_retain(0x40);
+        assert_eq!(colors.bits(), 0x83);
+        colors |= Colors::from_bits_retain(0x40);
+    }
+
+    #[test]
+    pub fn assign_ignored_reserved_bits(){
+        tlbf!(
+            #[tlbf(ignore_reserved_bits)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let mut colors = Colors::Red;
+        colors |= Colors::from_bits_retain(0x80);
+        colors ^= Colors::from_bits_retain(0x40);
+        assert_eq!(colors.bits(), 0xc1);
+    }
+
+    #[test]
+    pub fn minimal_cover(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        let brightness = |colors: Colors| colors.iter_indices().map(|index| index + 1).sum::<u32>();
+        assert_eq!(Colors::minimal_cover(|c| brightness(c) >= 3), Some(Colors::Green));
+        assert_eq!(Colors::minimal_cover(|c| brightness(c) >= 4), Some(Red | Green));
+        assert_eq!(Colors::minimal_cover(|c| c.contains(Blue)), Some(Colors::Blue));
+        assert_eq!(Colors::minimal_cover(|_| true), Some(Colors::EMPTY));
+        assert_eq!(Colors::minimal_cover(|c| brightness(c) > 6), None);
+    }
+
+    #[test]
+    pub fn when(){
+        use crate::{SetMember, When};
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert_eq!(When::<Red>::new(true).to_set(), Colors::Red);
+        assert_eq!(When::<Red>::new(false).to_set(), Colors::EMPTY);
+        assert_eq!(Green.and_set(When::<Red>::new(true)).and_set(When::<Blue>::new(false)), Red | Green);
+        assert!(Colors::Blue.contains(When::<Red>::new(false)));
+        assert!(!Colors::Blue.contains(When::<Red>::new(true)));
+        assert!(When::<Red>::new(false).eq_set(&Colors::EMPTY));
+    }
+
+    #[test]
+    pub fn matches_mask(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        assert!((Red | Green).matches_mask(0b101));
+        assert!((Red | Green).matches_mask(0b1000_0101));
+        assert!(Colors::from_bits_retain(0x81).matches_mask(0b001));
+        assert!(!Colors::from_bits_retain(0x81).matches_mask(0b011));
+        assert!(Colors::from_bits_retain(0x81) != 0b001);
+    }
+
+    #[test]
+    pub fn try_insert(){
+        use crate::ExclusivityError;
+        tlbf!(
+            pub Style: u8 {
+                pub Red,
+                pub Green,
+                pub Blue,
+                pub Bold,
+                pub Italic,
+            }
+            exclusive Color { Red, Green, Blue }
+            exclusive Font { Bold, Italic }
+        );
+        let mut style = Style::Red;
+        assert_eq!(style.try_insert(Bold), Ok(()));
+        assert_eq!(style.try_insert(Red), Ok(()));
+        assert_eq!(style.try_insert(Green), Err(ExclusivityError { conflict: Style::Red }));
+        assert_eq!(style.try_insert(Blue | Italic), Err(ExclusivityError { conflict: Red | Bold }));
+        assert_eq!(style, Red | Bold);
+        assert_eq!(format!("{}", ExclusivityError { conflict: 1 }), "conflicts with 1 in an exclusive group");
+
+        let mut style = Style::EMPTY;
+        assert_eq!(style.try_insert(Red | Green), Err(ExclusivityError { conflict: Red | Green }));
+        assert_eq!(style.try_insert(Style::Color), Err(ExclusivityError { conflict: Style::Color }));
+        assert_eq!(style, Style::EMPTY);
+        assert_eq!(style.try_insert(Red | Bold), Ok(()));
+        assert!(style.is_valid());
+
+        {
+            tlbf!(
+                pub Colors: u8 {
+                    pub Red,
+                    pub Blue,
                 }
-            };
-        )*
-    };
-}
+            );
+            let mut colors = Colors::Red;
+            assert_eq!(colors.try_insert(Blue), Ok(()));
+            assert_eq!(colors, Colors::ALL);
+        }
+    }
 
+    #[test]
+    pub fn const_set_algebra(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
+            }
+        );
+        const WARM: Colors = Colors::Red.union_const(Colors::Green);
+        const COOL: Colors = WARM.complement_const();
+        const GREEN: Colors = WARM.intersection_const(Colors::Green.union_const(Colors::Blue));
+        const RED: Colors = WARM.difference_const(GREEN);
+        const _: () = assert!(COOL.bits() == 0b010);
+        const _: () = assert!(Colors::from_bits_retain(0x80).complement_const().bits() == 0b111);
+        assert_eq!([WARM, COOL, GREEN, RED], [Red | Green, Colors::Blue, Colors::Green, Colors::Red]);
+    }
 
-/// Join bitflags at the type level.
-/// 
-/// ```
-/// # use tlbf::*;
-/// # tlbf!(
-/// #     pub Color: u64 {
-/// #         pub Red, Green, Blue,
-/// #     }
-/// # );
-/// let flags = tyflags!(Red|Blue);
-/// assert!(flags.contains(Color::Red));
-/// assert!(flags.contains(Color::Blue));
-/// assert!(!flags.contains(Color::Green));
-/// ```
-#[macro_export]
-macro_rules! tyflags {
-    ($expr: expr $(,)?) => {
-        $expr
-    };
-    ($first: expr $(,$expr: expr)* $(,)?) => {
-        $crate::Or<$first, $crate::type_join!($($expr),*)>
-    };
-}
+    #[test]
+    pub fn reverse_flags(){
+        tlbf!(
+            pub Colors: u8 {
+                pub Green = 5,
+                pub Red = 0,
+                pub Blue,
+            }
+        );
+        assert_eq!(Colors::Red.reverse_flags(), Colors::Green);
+        assert_eq!(Colors::Blue.reverse_flags(), Colors::Blue);
+        assert_eq!((Red | Blue).reverse_flags(), Blue | Green);
+        assert_eq!(Colors::ALL.reverse_flags(), Colors::ALL);
+        assert_eq!(Colors::from_bits_retain(0x81).reverse_flags(), Colors::from_bits_retain(0xa0));
+    }
 
-#[cfg(test)]
-mod test {
+    tlbf!(
+        mod generated_tests {
+            #[tlbf(test)]
+            #[tlbf(display_sep = ", ")]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                #[tlbf(rename = "green")]
+                pub Green = 6,
+            }
+        }
+    );
 
     tlbf!(
-        pub Unit1: u8 {
-            Hello
+        mod generated_named_tests {
+            #[tlbf(test = style_tests)]
+            pub Style: u16 {
+                pub Bold,
+                pub Italic,
+            }
         }
     );
+
     tlbf!(
-        pub Unit2: u8 {
-            Hiii,
+        mod generated_long_names {
+            #[tlbf(test)]
+            pub Interrupts: u64 {
+                InterruptEnable00,
+                InterruptEnable01,
+                InterruptEnable02,
+                InterruptEnable03,
+                InterruptEnable04,
+                InterruptEnable05,
+                InterruptEnable06,
+                InterruptEnable07,
+                InterruptEnable08,
+                InterruptEnable09,
+                InterruptEnable10,
+                InterruptEnable11,
+                InterruptEnable12,
+                InterruptEnable13,
+                InterruptEnable14,
+                InterruptEnable15,
+                InterruptEnable16,
+                InterruptEnable17,
+                InterruptEnable18,
+                InterruptEnable19,
+                InterruptEnable20,
+                InterruptEnable21,
+                InterruptEnable22,
+                InterruptEnable23,
+                InterruptEnable24,
+                InterruptEnable25,
+                InterruptEnable26,
+                InterruptEnable27,
+                InterruptEnable28,
+                InterruptEnable29,
+                InterruptEnable30,
+                InterruptEnable31,
+                InterruptEnable32,
+                InterruptEnable33,
+                InterruptEnable34,
+                InterruptEnable35,
+                InterruptEnable36,
+                InterruptEnable37,
+                InterruptEnable38,
+                InterruptEnable39,
+                InterruptEnable40,
+                InterruptEnable41,
+                InterruptEnable42,
+                InterruptEnable43,
+                InterruptEnable44,
+                InterruptEnable45,
+                InterruptEnable46,
+                InterruptEnable47,
+                InterruptEnable48,
+                InterruptEnable49,
+                InterruptEnable50,
+                InterruptEnable51,
+                InterruptEnable52,
+                InterruptEnable53,
+                InterruptEnable54,
+                InterruptEnable55,
+                InterruptEnable56,
+                InterruptEnable57,
+                InterruptEnable58,
+                InterruptEnable59,
+                InterruptEnable60,
+                InterruptEnable61,
+                InterruptEnable62,
+                InterruptEnable63,
+            }
         }
     );
+
     #[test]
-    pub fn test(){
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    pub fn subset_ord(){
+        use core::cmp::Ordering;
         tlbf!(
-            pub Mascot: u8 {
-                pub Ferris
+            #[tlbf(subset_ord)]
+            pub Colors: u8 {
+                pub Red,
+                pub Blue,
+                pub Green,
             }
         );
+        assert_eq!(Colors::Red.partial_cmp(&(Red | Blue)), Some(Ordering::Less));
+        assert_eq!((Red | Blue).partial_cmp(&Colors::Blue), Some(Ordering::Greater));
+        assert_eq!((Red | Blue).partial_cmp(&(Blue | Red)), Some(Ordering::Equal));
+        assert_eq!((Red | Blue).partial_cmp(&(Blue | Green)), None);
+        assert_eq!(Colors::EMPTY.partial_cmp(&Colors::ALL), Some(Ordering::Less));
+        assert!(Colors::Green <= Colors::ALL);
+        assert!(!(Colors::Red <= Colors::Green) && !(Colors::Red > Colors::Green));
+        assert_eq!(Colors::from_bits_retain(0x81).partial_cmp(&Colors::Red), Some(Ordering::Greater));
+    }
+
+    #[test]
+    pub fn many_documented_flags(){
         tlbf!(
-            #[derive(Default)]
-            LesserMascots: u8 {
-                #[repr(C)]
-                Gopher
+            pub Interrupts: u64 {
+                /// Interrupt line 0.
+                pub Irq00,
+                /// Interrupt line 1.
+                pub Irq01,
+                /// Interrupt line 2.
+                pub Irq02,
+                /// Interrupt line 3.
+                pub Irq03,
+                /// Interrupt line 4.
+                pub Irq04,
+                /// Interrupt line 5.
+                pub Irq05,
+                /// Interrupt line 6.
+                pub Irq06,
+                /// Interrupt line 7.
+                pub Irq07,
+                /// Interrupt line 8.
+                pub Irq08,
+                /// Interrupt line 9.
+                pub Irq09,
+                /// Interrupt line 10.
+                pub Irq10,
+                /// Interrupt line 11.
+                pub Irq11,
+                /// Interrupt line 12.
+                pub Irq12,
+                /// Interrupt line 13.
+                pub Irq13,
+                /// Interrupt line 14.
+                pub Irq14,
+                /// Interrupt line 15.
+                pub Irq15,
+                /// Interrupt line 16.
+                pub Irq16,
+                /// Interrupt line 17.
+                pub Irq17,
+                /// Interrupt line 18.
+                pub Irq18,
+                /// Interrupt line 19.
+                pub Irq19,
+                /// Interrupt line 20.
+                pub Irq20,
+                /// Interrupt line 21.
+                pub Irq21,
+                /// Interrupt line 22.
+                pub Irq22,
+                /// Interrupt line 23.
+                pub Irq23,
+                /// Interrupt line 24.
+                pub Irq24,
+                /// Interrupt line 25.
+                pub Irq25,
+                /// Interrupt line 26.
+                pub Irq26,
+                /// Interrupt line 27.
+                pub Irq27,
+                /// Interrupt line 28.
+                pub Irq28,
+                /// Interrupt line 29.
+                pub Irq29,
+                /// Interrupt line 30.
+                pub Irq30,
+                /// Interrupt line 31.
+                pub Irq31,
+                /// Interrupt line 32.
+                pub Irq32,
+                /// Interrupt line 33.
+                pub Irq33,
+                /// Interrupt line 34.
+                pub Irq34,
+                /// Interrupt line 35.
+                pub Irq35,
+                /// Interrupt line 36.
+                pub Irq36,
+                /// Interrupt line 37.
+                pub Irq37,
+                /// Interrupt line 38.
+                pub Irq38,
+                /// Interrupt line 39.
+                pub Irq39,
+                /// Interrupt line 40.
+                pub Irq40,
+                /// Interrupt line 41.
+                pub Irq41,
+                /// Interrupt line 42.
+                pub Irq42,
+                /// Interrupt line 43.
+                pub Irq43,
+                /// Interrupt line 44.
+                pub Irq44,
+                /// Interrupt line 45.
+                pub Irq45,
+                /// Interrupt line 46.
+                pub Irq46,
+                /// Interrupt line 47.
+                pub Irq47,
+                /// Interrupt line 48.
+                pub Irq48,
+                /// Interrupt line 49.
+                pub Irq49,
+                /// Interrupt line 50.
+                pub Irq50,
+                /// Interrupt line 51.
+                pub Irq51,
+                /// Interrupt line 52.
+                pub Irq52,
+                /// Interrupt line 53.
+                pub Irq53,
+                /// Interrupt line 54.
+                pub Irq54,
+                /// Interrupt line 55.
+                pub Irq55,
+                /// Interrupt line 56.
+                pub Irq56,
+                /// Interrupt line 57.
+                pub Irq57,
+                /// Interrupt line 58.
+                pub Irq58,
+                /// Interrupt line 59.
+                pub Irq59,
+                /// Interrupt line 60.
+                pub Irq60,
+                /// Interrupt line 61.
+                pub Irq61,
+                /// Interrupt line 62.
+                pub Irq62,
+                /// Interrupt line 63.
+                pub Irq63,
             }
         );
-        assert_eq!(Mascot::all(), Mascot::Ferris);
+        assert_eq!(Interrupts::COUNT, 64);
+        assert_eq!(Interrupts::ALL.bits(), u64::MAX);
+        assert_eq!(Interrupts::Irq05.bits(), 1 << 5);
+        assert_eq!(Interrupts::Irq63.bits(), 1 << 63);
+        assert_eq!(Interrupts::names()[62], "Irq62");
+    }
 
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic = "member has several flags of one exclusive group")]
+    pub fn set_exclusive_several(){
         tlbf!(
-            pub Colors: u8 {
+            pub Style: u8 {
                 pub Red,
-                pub Blue,
                 pub Green,
+                pub Bold,
             }
+            exclusive Color { Red, Green }
         );
-        assert_eq!(Colors::all(), Colors::Red|Colors::Blue|Colors::Green);
+        let mut style = Style::Bold;
+        style.set_exclusive(Red | Bold);
+        assert_eq!(style, Red | Bold);
+        style.set_exclusive(Red | Green);
     }
-}
\ No newline at end of file
+}